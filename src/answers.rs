@@ -0,0 +1,82 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::runner::DayRun;
+use aoc2020::solution::{Answer, Part};
+
+#[derive(Debug, PartialEq)]
+pub enum Verdict {
+    Correct,
+    Wrong(String),
+    Unknown,
+}
+
+// Recorded answers live next to the puzzle input, e.g. data/NN/answers.txt
+pub fn answers_path(input_path: &str) -> PathBuf {
+    Path::new(input_path).with_file_name("answers.txt")
+}
+
+/*
+ * Answers file format, one line per part (an empty line means not known yet):
+ * 1016964
+ * 182588480
+ */
+pub fn read_answers(input_path: &str) -> io::Result<Vec<String>> {
+    let content = fs::read_to_string(answers_path(input_path))?;
+    Ok(content.lines().map(|l| l.trim().to_string()).collect())
+}
+
+pub fn verify(answer: &Answer, part: Part, expected: &[String]) -> Verdict {
+    match expected.get(part.number() as usize - 1) {
+        Some(e) if e.is_empty() => Verdict::Unknown,
+        Some(e) if *e == answer.to_string() => Verdict::Correct,
+        Some(e) => Verdict::Wrong(e.clone()),
+        None => Verdict::Unknown,
+    }
+}
+
+// Prints the verdict of every part and returns whether none of them was wrong.
+pub fn print_checks(runs: &[DayRun], expected: &[Vec<String>]) -> bool {
+    let mut passed = true;
+    for (run, expected) in runs.iter().zip(expected) {
+        for part in run.parts.iter() {
+            let prefix = format!("Day {:02} part {}", run.day, part.part);
+            match verify(&part.answer, part.part, expected) {
+                Verdict::Correct => println!("{}: ok ({})", prefix, part.answer),
+                Verdict::Unknown => println!("{}: no recorded answer ({})", prefix, part.answer),
+                Verdict::Wrong(e) => {
+                    passed = false;
+                    println!("{}: FAILED, expected {}, got {}", prefix, e, part.answer);
+                }
+            }
+        }
+    }
+    passed
+}
+
+#[test]
+fn test_answers_path() {
+    assert_eq!(
+        PathBuf::from("data/01/answers.txt"),
+        answers_path("data/01/input.txt")
+    );
+}
+
+#[test]
+fn test_verify() {
+    let expected = vec!["205".to_string(), "".to_string()];
+    assert_eq!(
+        Verdict::Correct,
+        verify(&Answer::from(205), Part::One, &expected)
+    );
+    assert_eq!(
+        Verdict::Wrong("205".to_string()),
+        verify(&Answer::from(206), Part::One, &expected)
+    );
+    assert_eq!(
+        Verdict::Unknown,
+        verify(&Answer::from(1), Part::Two, &expected)
+    );
+    assert_eq!(Verdict::Unknown, verify(&Answer::from(1), Part::Two, &[]));
+}
@@ -0,0 +1,70 @@
+use std::str::FromStr;
+
+use aoc2020::day_02::Rejection;
+
+#[derive(Debug, PartialEq)]
+pub enum ReportFormat {
+    Text,
+    Csv,
+}
+
+impl FromStr for ReportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(ReportFormat::Text),
+            "csv" => Ok(ReportFormat::Csv),
+            _ => Err(format!(
+                "invalid report format `{}`, expected text or csv",
+                s
+            )),
+        }
+    }
+}
+
+// Prints one line per password and policy it breaks, followed by the number
+// of valid passwords in text format.
+pub fn print_report(rejections: &[Rejection], valid: usize, total: usize, format: &ReportFormat) {
+    match format {
+        ReportFormat::Text => {
+            for r in rejections {
+                println!("Line {}: {} -- {}: {}", r.line, r.rule, r.policy, r.reason);
+            }
+            println!("{} of {} passwords valid", valid, total);
+        }
+        ReportFormat::Csv => {
+            println!("line,rule,policy,reason");
+            for r in rejections {
+                let fields = [
+                    r.line.to_string(),
+                    r.rule.to_string(),
+                    r.policy.clone(),
+                    r.reason.clone(),
+                ];
+                let fields: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+                println!("{}", fields.join(","));
+            }
+        }
+    }
+}
+
+// Quotes a field containing a separator, a quote or a line break, doubling
+// its quotes.
+fn csv_field(field: &str) -> String {
+    if field.contains(&[',', '"', '\n', '\r'][..]) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[test]
+fn test_csv_field() {
+    assert_eq!("1-3 a: abcde", csv_field("1-3 a: abcde"));
+    assert_eq!(
+        "\"missing required chars 'x', 'y'\"",
+        csv_field("missing required chars 'x', 'y'")
+    );
+    assert_eq!("\"a \"\"b\"\"\"", csv_field("a \"b\""));
+}
@@ -1,42 +1,238 @@
-use itertools::Itertools;
-use std::collections::HashSet;
-use std::fs::File;
-use std::io::{prelude::*, BufReader};
-
-const FILEPATH: &str = "data/01/input.txt";
-
-pub fn first_solution() {
-    let file = File::open(FILEPATH).unwrap();
-    let reader = BufReader::new(file);
-
-    let numbers = reader
-        .lines()
-        .map(|w| w.unwrap().parse::<i32>().unwrap())
-        .collect::<HashSet<i32>>();
-
-    for num in numbers.iter() {
-        let candidate = 2020 - num;
-        if numbers.contains(&candidate) {
-            println!("Solution: {}", num * candidate);
-            return;
-        }
+//! Day 1: Report Repair
+
+use std::convert::TryFrom;
+
+use crate::error::{parse_field, parse_lines, ParseError};
+use crate::solution::{Answer, Solution};
+
+/// The expense report entries, sorted.
+#[derive(Debug)]
+pub struct Puzzle {
+    numbers: Vec<i64>,
+}
+
+impl Puzzle {
+    pub fn numbers(&self) -> &[i64] {
+        &self.numbers
+    }
+}
+
+/// Parses the puzzle input.
+pub fn parse(input: &str) -> Result<Puzzle, ParseError> {
+    let mut numbers = parse_lines(input, |w| parse_field(w, w, "a number"))?;
+    numbers.sort_unstable();
+    Ok(Puzzle { numbers })
+}
+
+/// Product of the two entries that sum to 2020.
+pub fn part_one(puzzle: &Puzzle) -> Option<i64> {
+    find_k_sum(&puzzle.numbers, 2, 2020).and_then(|entries| product(&entries))
+}
+
+/// Product of the three entries that sum to 2020.
+pub fn part_two(puzzle: &Puzzle) -> Option<i64> {
+    find_k_sum(&puzzle.numbers, 3, 2020).and_then(|entries| product(&entries))
+}
+
+/// Product of `entries`, or `None` if it overflows.
+pub fn product(entries: &[i64]) -> Option<i64> {
+    entries.iter().try_fold(1i64, |p, &e| p.checked_mul(e))
+}
+
+/// Parses the puzzle input and solves both parts.
+pub fn solve(input: &str) -> Result<(Option<i64>, Option<i64>), ParseError> {
+    let puzzle = parse(input)?;
+    Ok((part_one(&puzzle), part_two(&puzzle)))
+}
+
+impl Solution for Puzzle {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        parse(input)
+    }
+
+    fn part_one(&self) -> Answer {
+        part_one(self).into()
+    }
+
+    fn part_two(&self) -> Answer {
+        part_two(self).into()
     }
 }
 
-pub fn second_solution() {
-    let file = File::open(FILEPATH).unwrap();
-    let reader = BufReader::new(file);
+/// `k` entries of `numbers` that sum to `target`, in increasing order. An
+/// entry can only be used as many times as it appears in `numbers`.
+///
+/// The entries are sorted, then each candidate for the smallest entry reduces
+/// the search to `k - 1` entries among the larger ones, down to a two-pointer
+/// scan for the last two: `O(n^(k-1))` steps for `k >= 2`.
+pub fn find_k_sum(numbers: &[i64], k: usize, target: i64) -> Option<Vec<i64>> {
+    let mut found = None;
+    visit_k_sums(numbers, k, target, &mut |entries| {
+        found = Some(entries.to_vec());
+        true
+    });
+    found
+}
+
+/// Every distinct set of `k` entries of `numbers` that sum to `target`, each
+/// in increasing order, as found by `find_k_sum`. More than one means the
+/// answer depends on which set is picked.
+pub fn find_all_k_sums(numbers: &[i64], k: usize, target: i64) -> Vec<Vec<i64>> {
+    let mut all = vec![];
+    visit_k_sums(numbers, k, target, &mut |entries| {
+        all.push(entries.to_vec());
+        false
+    });
+    all
+}
 
-    let numbers = reader
-        .lines()
-        .map(|w| w.unwrap().parse::<i32>().unwrap())
-        .collect::<HashSet<i32>>();
+// Calls `visit` with each distinct set of entries summing to `target`, in
+// increasing order, until it returns true.
+fn visit_k_sums(numbers: &[i64], k: usize, target: i64, visit: &mut dyn FnMut(&[i64]) -> bool) {
+    let mut sorted = numbers.to_vec();
+    sorted.sort_unstable();
+    k_sums(&sorted, k, target as i128, &mut vec![], visit);
+}
 
-    for pair in numbers.iter().combinations(2) {
-        let candidate = 2020 - (pair[0] + pair[1]);
-        if numbers.contains(&candidate) {
-            println!("Solution: {}", candidate * pair[0] * pair[1]);
-            return;
+// Sets of entries of the sorted `numbers` summing to `target`, appended to
+// `prefix`. Returns whether `visit` asked to stop. Sums are computed in i128 so
+// that they cannot overflow, whatever the entries and target.
+fn k_sums(
+    numbers: &[i64],
+    k: usize,
+    target: i128,
+    prefix: &mut Vec<i64>,
+    visit: &mut dyn FnMut(&[i64]) -> bool,
+) -> bool {
+    match k {
+        0 => target == 0 && visit(prefix),
+        1 => match i64::try_from(target) {
+            Ok(n) => numbers.binary_search(&n).is_ok() && visit_with(prefix, &[n], visit),
+            Err(_) => false,
+        },
+        2 => two_sums(numbers, target, prefix, visit),
+        _ => {
+            for (i, &n) in numbers.iter().enumerate() {
+                // Duplicates give the same sums as the first of them
+                if i > 0 && numbers[i - 1] == n {
+                    continue;
+                }
+                prefix.push(n);
+                let stop = k_sums(&numbers[i + 1..], k - 1, target - n as i128, prefix, visit);
+                prefix.pop();
+                if stop {
+                    return true;
+                }
+            }
+            false
         }
     }
 }
+
+fn two_sums(
+    numbers: &[i64],
+    target: i128,
+    prefix: &mut Vec<i64>,
+    visit: &mut dyn FnMut(&[i64]) -> bool,
+) -> bool {
+    if numbers.len() < 2 {
+        return false;
+    }
+    let (mut low, mut high) = (0, numbers.len() - 1);
+    while low < high {
+        let sum = numbers[low] as i128 + numbers[high] as i128;
+        if sum == target {
+            if visit_with(prefix, &[numbers[low], numbers[high]], visit) {
+                return true;
+            }
+            while low < high && numbers[low] == numbers[low + 1] {
+                low += 1;
+            }
+            low += 1;
+        } else if sum < target {
+            low += 1;
+        } else {
+            high -= 1;
+        }
+    }
+    false
+}
+
+fn visit_with(
+    prefix: &mut Vec<i64>,
+    entries: &[i64],
+    visit: &mut dyn FnMut(&[i64]) -> bool,
+) -> bool {
+    let length = prefix.len();
+    prefix.extend(entries);
+    let stop = visit(prefix);
+    prefix.truncate(length);
+    stop
+}
+
+#[test]
+fn test_find_k_sum() {
+    let numbers = [1721, 979, 366, 299, 675, 1456];
+    assert_eq!(Some(vec![299, 1721]), find_k_sum(&numbers, 2, 2020));
+    assert_eq!(Some(vec![366, 675, 979]), find_k_sum(&numbers, 3, 2020));
+    assert_eq!(
+        Some(vec![299, 366, 979, 1456]),
+        find_k_sum(&numbers, 4, 3100)
+    );
+    assert_eq!(None, find_k_sum(&numbers, 2, 2));
+    assert_eq!(Some(vec![979]), find_k_sum(&numbers, 1, 979));
+    assert_eq!(Some(vec![]), find_k_sum(&numbers, 0, 0));
+}
+
+#[test]
+fn test_find_k_sum_duplicates() {
+    assert_eq!(None, find_k_sum(&[1010, 5, 7], 2, 2020));
+    assert_eq!(
+        Some(vec![1010, 1010]),
+        find_k_sum(&[1010, 5, 1010], 2, 2020)
+    );
+    assert_eq!(Some(vec![2, 2, 2]), find_k_sum(&[2, 2, 2, 1], 3, 6));
+    assert_eq!(None, find_k_sum(&[2, 2, 1], 3, 6));
+}
+
+#[test]
+fn test_find_all_k_sums() {
+    let numbers = [1, 3, 2, 2, 1, 3, 4];
+    assert_eq!(
+        vec![vec![1, 4], vec![2, 3]],
+        find_all_k_sums(&numbers, 2, 5)
+    );
+    assert_eq!(
+        vec![vec![1, 1, 4], vec![1, 2, 3], vec![2, 2, 2]],
+        find_all_k_sums(&[1, 1, 2, 2, 2, 3, 4], 3, 6)
+    );
+    assert_eq!(
+        vec![vec![1, 1, 4], vec![1, 2, 3]],
+        find_all_k_sums(&numbers, 3, 6)
+    );
+    assert!(find_all_k_sums(&numbers, 2, 100).is_empty());
+}
+
+#[test]
+fn test_find_k_sum_extreme_values() {
+    let numbers = [i64::MAX, 5, i64::MIN, -3];
+    assert_eq!(None, find_k_sum(&numbers, 2, 12));
+    assert_eq!(Some(vec![i64::MIN, i64::MAX]), find_k_sum(&numbers, 2, -1));
+    assert_eq!(
+        Some(vec![-3, i64::MAX]),
+        find_k_sum(&numbers, 2, i64::MAX - 3)
+    );
+    assert_eq!(
+        Some(vec![i64::MIN, 5, i64::MAX]),
+        find_k_sum(&numbers, 3, 4)
+    );
+    assert_eq!(None, find_k_sum(&numbers, 3, i64::MIN));
+    assert_eq!(Some(vec![i64::MIN]), find_k_sum(&numbers, 1, i64::MIN));
+}
+
+#[test]
+fn test_product_overflow() {
+    let puzzle = parse("4000000000\n-3999997980").unwrap();
+    assert_eq!(None, part_one(&puzzle));
+    assert_eq!(Some(-6), product(&[2, -3]));
+}
@@ -1,39 +1,108 @@
-use std::fs::File;
-use std::io::{prelude::*, BufReader};
+//! Day 2: Password Philosophy
+
+use std::fmt;
 
 use lazy_static::lazy_static;
 use regex::Regex;
 
-const FILEPATH: &str = "data/02/input.txt";
+use crate::error::{parse_field, parse_lines, ParseError};
+use crate::solution::{Answer, Solution};
+
 lazy_static! {
     static ref RE: Regex = Regex::new(r"^(\d+)\-(\d+) (\w): (\w+)$").expect("Invalid regex");
 }
 
-pub fn first_solution() {
-    let file = File::open(FILEPATH).expect("File not found");
-    let reader = BufReader::new(file);
+/// The password database: one policy and password per line.
+#[derive(Debug)]
+pub struct Puzzle {
+    rules: Vec<PasswordRule>,
+}
+
+impl Puzzle {
+    pub fn rules(&self) -> &[PasswordRule] {
+        &self.rules
+    }
+}
+
+/// Parses the puzzle input.
+pub fn parse(input: &str) -> Result<Puzzle, ParseError> {
+    Ok(Puzzle {
+        rules: parse_lines(input, password_db_parser)?,
+    })
+}
+
+/// Number of passwords valid under the sled rental policy.
+pub fn part_one(puzzle: &Puzzle) -> usize {
+    count_valid(puzzle, &[Box::new(SledRental)])
+}
 
-    let count = reader
-        .lines()
-        .map(|l| password_db_parser(l.unwrap()))
-        .filter(|pw| pw.first_rule_is_valid())
-        .count();
-    println!("Solution: {}", count);
+/// Number of passwords valid under the Official Toboggan Corporate policy.
+pub fn part_two(puzzle: &Puzzle) -> usize {
+    count_valid(puzzle, &[Box::new(Toboggan)])
 }
 
-pub fn second_solution() {
-    let file = File::open(FILEPATH).expect("File not found");
-    let reader = BufReader::new(file);
+/// Number of passwords valid under every one of `policies`.
+pub fn count_valid(puzzle: &Puzzle, policies: &[Box<dyn PasswordPolicy>]) -> usize {
+    puzzle
+        .rules
+        .iter()
+        .filter(|rule| policies.iter().all(|policy| policy.is_valid(rule)))
+        .count()
+}
 
-    let count = reader
-        .lines()
-        .map(|l| password_db_parser(l.unwrap()))
-        .filter(|pw| pw.second_rule_is_valid())
-        .count();
-    println!("Solution: {}", count);
+/// A password rejected by a policy, for the audit report.
+#[derive(Debug)]
+pub struct Rejection<'a> {
+    /// Line of the password in the database, counting from 1.
+    pub line: usize,
+    pub rule: &'a PasswordRule,
+    pub policy: String,
+    pub reason: String,
+}
+
+/// Every password breaking one of `policies`, with one rejection per policy
+/// it breaks, in database order.
+pub fn audit<'a>(puzzle: &'a Puzzle, policies: &[Box<dyn PasswordPolicy>]) -> Vec<Rejection<'a>> {
+    let mut rejections = vec![];
+    for (i, rule) in puzzle.rules.iter().enumerate() {
+        for policy in policies {
+            if let Some(reason) = policy.violation(rule) {
+                rejections.push(Rejection {
+                    line: i + 1,
+                    rule,
+                    policy: policy.name(),
+                    reason,
+                });
+            }
+        }
+    }
+    rejections
 }
 
-struct PasswordRule {
+/// Parses the puzzle input and solves both parts.
+pub fn solve(input: &str) -> Result<(usize, usize), ParseError> {
+    let puzzle = parse(input)?;
+    Ok((part_one(&puzzle), part_two(&puzzle)))
+}
+
+impl Solution for Puzzle {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        parse(input)
+    }
+
+    fn part_one(&self) -> Answer {
+        part_one(self).into()
+    }
+
+    fn part_two(&self) -> Answer {
+        part_two(self).into()
+    }
+}
+
+/// A line of the password database: the policy numbers and letter, and the
+/// password they apply to.
+#[derive(Debug)]
+pub struct PasswordRule {
     first: usize,
     second: usize,
     ch: char,
@@ -41,26 +110,226 @@ struct PasswordRule {
 }
 
 impl PasswordRule {
-    fn first_rule_is_valid(&self) -> bool {
-        let count = self.password.chars().filter(|c| c == &self.ch).count();
-        self.first <= count && count <= self.second
+    pub fn first(&self) -> usize {
+        self.first
+    }
+
+    pub fn second(&self) -> usize {
+        self.second
+    }
+
+    pub fn ch(&self) -> char {
+        self.ch
+    }
+
+    pub fn password(&self) -> &str {
+        &self.password
+    }
+
+    fn count(&self, ch: char) -> usize {
+        self.password.chars().filter(|&c| c == ch).count()
+    }
+
+    // Whether the letter is at `position`, counting from 1.
+    fn has_ch_at(&self, position: usize) -> bool {
+        position
+            .checked_sub(1)
+            .and_then(|i| self.password.chars().nth(i))
+            == Some(self.ch)
+    }
+}
+
+/// A rule a password must follow to be valid.
+pub trait PasswordPolicy {
+    /// Name of the policy, in the format read by `parse_policy`.
+    fn name(&self) -> String;
+
+    /// Why the password of `rule` breaks the policy, if it does.
+    fn violation(&self, rule: &PasswordRule) -> Option<String>;
+
+    fn is_valid(&self, rule: &PasswordRule) -> bool {
+        self.violation(rule).is_none()
+    }
+}
+
+/// The letter appears between `first` and `second` times.
+pub struct SledRental;
+
+impl PasswordPolicy for SledRental {
+    fn name(&self) -> String {
+        "sled".to_string()
+    }
+
+    fn violation(&self, rule: &PasswordRule) -> Option<String> {
+        let count = rule.count(rule.ch);
+        if rule.first <= count && count <= rule.second {
+            return None;
+        }
+        Some(format!(
+            "char '{}' appears {}, allowed {}-{}",
+            rule.ch,
+            times(count),
+            rule.first,
+            rule.second
+        ))
+    }
+}
+
+/// The letter is at exactly one of the positions `first` and `second`,
+/// counting from 1.
+pub struct Toboggan;
+
+impl PasswordPolicy for Toboggan {
+    fn name(&self) -> String {
+        "toboggan".to_string()
+    }
+
+    fn violation(&self, rule: &PasswordRule) -> Option<String> {
+        match (rule.has_ch_at(rule.first), rule.has_ch_at(rule.second)) {
+            (true, true) => Some(format!(
+                "char '{}' at both positions {} and {}",
+                rule.ch, rule.first, rule.second
+            )),
+            (false, false) => Some(format!(
+                "char '{}' at neither position {} nor {}",
+                rule.ch, rule.first, rule.second
+            )),
+            _ => None,
+        }
+    }
+}
+
+/// The letter appears exactly this many times.
+pub struct ExactCount(pub usize);
+
+impl PasswordPolicy for ExactCount {
+    fn name(&self) -> String {
+        format!("exact-count={}", self.0)
     }
 
-    fn second_rule_is_valid(&self) -> bool {
-        // Only valid input is expected
-        let first = self
-            .password
-            .chars()
-            .nth(self.first - 1)
-            .expect("index error: invalid input")
-            == self.ch;
-        let second = self
-            .password
-            .chars()
-            .nth(self.second - 1)
-            .expect("index error: invalid input")
-            == self.ch;
-        (first || second) && first != second
+    fn violation(&self, rule: &PasswordRule) -> Option<String> {
+        let count = rule.count(rule.ch);
+        if count == self.0 {
+            return None;
+        }
+        Some(format!(
+            "char '{}' appears {}, expected {}",
+            rule.ch,
+            times(count),
+            self.0
+        ))
+    }
+}
+
+/// The password does not contain this char.
+pub struct ForbiddenChar(pub char);
+
+impl PasswordPolicy for ForbiddenChar {
+    fn name(&self) -> String {
+        format!("forbid={}", self.0)
+    }
+
+    fn violation(&self, rule: &PasswordRule) -> Option<String> {
+        match rule.count(self.0) {
+            0 => None,
+            count => Some(format!(
+                "forbidden char '{}' appears {}",
+                self.0,
+                times(count)
+            )),
+        }
+    }
+}
+
+/// The password has at least this many chars.
+pub struct MinLength(pub usize);
+
+impl PasswordPolicy for MinLength {
+    fn name(&self) -> String {
+        format!("min-length={}", self.0)
+    }
+
+    fn violation(&self, rule: &PasswordRule) -> Option<String> {
+        let length = rule.password.chars().count();
+        if length >= self.0 {
+            return None;
+        }
+        Some(format!(
+            "password has {} chars, expected at least {}",
+            length, self.0
+        ))
+    }
+}
+
+/// The password contains every one of these chars.
+pub struct RequiredChars(pub Vec<char>);
+
+impl PasswordPolicy for RequiredChars {
+    fn name(&self) -> String {
+        format!("require={}", self.0.iter().collect::<String>())
+    }
+
+    fn violation(&self, rule: &PasswordRule) -> Option<String> {
+        let missing: Vec<String> = self
+            .0
+            .iter()
+            .filter(|&&ch| !rule.password.contains(ch))
+            .map(|ch| format!("'{}'", ch))
+            .collect();
+        if missing.is_empty() {
+            return None;
+        }
+        Some(format!("missing required chars {}", missing.join(", ")))
+    }
+}
+
+fn times(count: usize) -> String {
+    match count {
+        1 => "once".to_string(),
+        _ => format!("{} times", count),
+    }
+}
+
+/// Parses a policy from the command line: `sled`, `toboggan`,
+/// `exact-count=N`, `forbid=C`, `min-length=N` or `require=CHARS`.
+pub fn parse_policy(spec: &str) -> Result<Box<dyn PasswordPolicy>, String> {
+    let (name, value) = match spec.split_once('=') {
+        Some((name, value)) => (name, Some(value)),
+        None => (spec, None),
+    };
+    let number = |value: &str| {
+        value
+            .parse()
+            .map_err(|_| format!("invalid policy `{}`, expected a number", spec))
+    };
+    match (name, value) {
+        ("sled", None) => Ok(Box::new(SledRental)),
+        ("toboggan", None) => Ok(Box::new(Toboggan)),
+        ("exact-count", Some(value)) => Ok(Box::new(ExactCount(number(value)?))),
+        ("min-length", Some(value)) => Ok(Box::new(MinLength(number(value)?))),
+        ("forbid", Some(value)) if value.chars().count() == 1 => {
+            Ok(Box::new(ForbiddenChar(value.chars().next().unwrap())))
+        }
+        ("forbid", Some(_)) => Err(format!("invalid policy `{}`, expected a char", spec)),
+        ("require", Some(value)) if !value.is_empty() => {
+            Ok(Box::new(RequiredChars(value.chars().collect())))
+        }
+        ("require", Some(_)) => Err(format!("invalid policy `{}`, expected chars", spec)),
+        _ => Err(format!(
+            "invalid policy `{}`, expected sled, toboggan, exact-count=N, forbid=C, min-length=N or require=CHARS",
+            spec
+        )),
+    }
+}
+
+// Same format as in the password database.
+impl fmt::Display for PasswordRule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}-{} {}: {}",
+            self.first, self.second, self.ch, self.password
+        )
     }
 }
 
@@ -68,22 +337,22 @@ impl PasswordRule {
  * Password rule input format:
  * 1-3 a: abcde
  */
-fn password_db_parser(line: String) -> PasswordRule {
-    let groups = RE.captures(&line).expect("Invalid input");
-    PasswordRule {
-        first: groups[1].parse().expect("expected `first` to be numeric"),
-        second: groups[2].parse().expect("expected `second` to be numeric"),
-        ch: groups[3].parse().expect("expected `ch` to be a char"),
-        password: groups[4]
-            .parse()
-            .expect("expected `password` to be a string"),
-    }
+fn password_db_parser(line: &str) -> Result<PasswordRule, ParseError> {
+    let groups = RE.captures(line).ok_or_else(|| {
+        ParseError::new(line, line, "expected `<first>-<second> <char>: <password>`")
+    })?;
+    Ok(PasswordRule {
+        first: parse_field(line, &groups[1], "a number")?,
+        second: parse_field(line, &groups[2], "a number")?,
+        ch: parse_field(line, &groups[3], "a char")?,
+        password: groups[4].to_string(),
+    })
 }
 
 #[test]
 fn test_password_db_parser() {
     let exemple = String::from("1-3 a: abcde");
-    let pw_rule = password_db_parser(exemple);
+    let pw_rule = password_db_parser(&exemple).unwrap();
     assert_eq!(1, pw_rule.first);
     assert_eq!(3, pw_rule.second);
     assert_eq!('a', pw_rule.ch);
@@ -92,42 +361,136 @@ fn test_password_db_parser() {
 
 #[test]
 fn test_password_first_rule_is_valid() {
-    let pw_rule = password_db_parser(String::from("1-3 b: aabbbcd"));
-    assert_eq!(true, pw_rule.first_rule_is_valid());
+    let pw_rule = password_db_parser("1-3 b: aabbbcd").unwrap();
+    assert!(SledRental.is_valid(&pw_rule));
 }
 
 #[test]
 fn test_password_first_rule_is_invalid_missing_character() {
-    let pw_rule = password_db_parser(String::from("1-3 b: aacd"));
-    assert_eq!(false, pw_rule.first_rule_is_valid());
+    let pw_rule = password_db_parser("1-3 b: aacd").unwrap();
+    assert!(!SledRental.is_valid(&pw_rule));
 }
 
 #[test]
 fn test_password_first_rule_is_invalid_by_max() {
-    let pw_rule = password_db_parser(String::from("1-2 b: aabbbcd"));
-    assert_eq!(false, pw_rule.first_rule_is_valid());
+    let pw_rule = password_db_parser("1-2 b: aabbbcd").unwrap();
+    assert!(!SledRental.is_valid(&pw_rule));
 }
 
 #[test]
 fn test_password_first_rule_is_invalid_by_min() {
-    let pw_rule = password_db_parser(String::from("2-3 b: aabcd"));
-    assert_eq!(false, pw_rule.first_rule_is_valid());
+    let pw_rule = password_db_parser("2-3 b: aabcd").unwrap();
+    assert!(!SledRental.is_valid(&pw_rule));
 }
 
 #[test]
 fn test_password_second_rule_is_valid() {
-    let pw_rule = password_db_parser(String::from("1-3 a: abcd"));
-    assert_eq!(true, pw_rule.second_rule_is_valid());
+    let pw_rule = password_db_parser("1-3 a: abcd").unwrap();
+    assert!(Toboggan.is_valid(&pw_rule));
 }
 
 #[test]
 fn test_password_second_rule_is_invalid_missing_char() {
-    let pw_rule = password_db_parser(String::from("1-3 b: abcd"));
-    assert_eq!(false, pw_rule.second_rule_is_valid());
+    let pw_rule = password_db_parser("1-3 b: abcd").unwrap();
+    assert!(!Toboggan.is_valid(&pw_rule));
 }
 
 #[test]
 fn test_password_second_rule_is_invalid_missing_duplicated() {
-    let pw_rule = password_db_parser(String::from("1-3 a: aaa"));
-    assert_eq!(false, pw_rule.second_rule_is_valid());
+    let pw_rule = password_db_parser("1-3 a: aaa").unwrap();
+    assert!(!Toboggan.is_valid(&pw_rule));
+}
+
+#[test]
+fn test_password_db_parser_invalid_number() {
+    let err = password_db_parser("1-99999999999999999999 a: abcde").unwrap_err();
+    assert_eq!(3, err.column);
+}
+
+#[test]
+fn test_password_db_parser_invalid_format() {
+    let err = password_db_parser("1-3 abcde").unwrap_err();
+    assert_eq!(1, err.column);
+}
+
+#[test]
+fn test_password_second_rule_out_of_range() {
+    let pw_rule = password_db_parser("0-9 a: abc").unwrap();
+    assert!(!Toboggan.is_valid(&pw_rule));
+}
+
+#[test]
+fn test_other_policies() {
+    let pw_rule = password_db_parser("1-3 b: aabbbcd").unwrap();
+    assert!(ExactCount(3).is_valid(&pw_rule));
+    assert!(!ExactCount(2).is_valid(&pw_rule));
+    assert!(ForbiddenChar('x').is_valid(&pw_rule));
+    assert!(!ForbiddenChar('c').is_valid(&pw_rule));
+    assert!(MinLength(7).is_valid(&pw_rule));
+    assert!(!MinLength(8).is_valid(&pw_rule));
+    assert!(RequiredChars(vec!['a', 'd']).is_valid(&pw_rule));
+    assert!(!RequiredChars(vec!['a', 'e']).is_valid(&pw_rule));
+}
+
+#[test]
+fn test_count_valid() {
+    let puzzle = parse("1-3 a: abcde\n1-3 b: cdefg\n2-9 c: ccccccccc").unwrap();
+    assert_eq!(2, part_one(&puzzle));
+    assert_eq!(1, part_two(&puzzle));
+    let policies = vec![
+        parse_policy("sled").unwrap(),
+        parse_policy("forbid=e").unwrap(),
+    ];
+    assert_eq!(1, count_valid(&puzzle, &policies));
+}
+
+#[test]
+fn test_parse_policy_errors() {
+    assert!(parse_policy("require=xy").is_ok());
+    assert!(parse_policy("sled=1").is_err());
+    assert!(parse_policy("forbid=ab").is_err());
+    assert_eq!(
+        "invalid policy `min-length=x`, expected a number",
+        parse_policy("min-length=x").err().unwrap()
+    );
+}
+
+#[test]
+fn test_violations() {
+    let pw_rule = password_db_parser("1-3 b: abbbbc").unwrap();
+    assert_eq!(
+        Some("char 'b' appears 4 times, allowed 1-3".to_string()),
+        SledRental.violation(&pw_rule)
+    );
+    assert_eq!(
+        Some("char 'b' at both positions 1 and 3".to_string()),
+        password_db_parser("1-3 b: bab")
+            .map(|r| Toboggan.violation(&r))
+            .unwrap()
+    );
+    assert_eq!(
+        Some("forbidden char 'a' appears once".to_string()),
+        ForbiddenChar('a').violation(&pw_rule)
+    );
+    assert_eq!(
+        Some("missing required chars 'x', 'y'".to_string()),
+        RequiredChars(vec!['x', 'a', 'y']).violation(&pw_rule)
+    );
+    assert_eq!(None, MinLength(6).violation(&pw_rule));
+}
+
+#[test]
+fn test_audit() {
+    let puzzle = parse("1-3 a: abcde\n1-3 b: cdefg\n2-9 c: ccccccccc").unwrap();
+    let policies = vec![
+        parse_policy("sled").unwrap(),
+        parse_policy("toboggan").unwrap(),
+    ];
+    let rejections = audit(&puzzle, &policies);
+    let lines: Vec<(usize, &str)> = rejections
+        .iter()
+        .map(|r| (r.line, r.policy.as_str()))
+        .collect();
+    assert_eq!(vec![(2, "sled"), (2, "toboggan"), (3, "toboggan")], lines);
+    assert_eq!("2-9 c: ccccccccc", rejections[2].rule.to_string());
 }
@@ -1,39 +1,61 @@
-use std::fs::File;
-use std::io::{prelude::*, BufReader};
+//! Day 3: Toboggan Trajectory
 
-const FILEPATH: &str = "data/03/input.txt";
+use crate::error::ParseError;
+use crate::grid::Grid;
+use crate::solution::{Answer, Solution};
 
-pub fn first_solution() {
-    let map = create_map_from_file();
-    let count = count_trees_from_slope(&map, (3, 1));
-    println!("{}", count);
+/// The map of open squares and trees.
+#[derive(Debug)]
+pub struct Puzzle {
+    map: Grid<Square>,
 }
 
-pub fn second_solution() {
-    let map = create_map_from_file();
-    let slopes = vec![(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
-    let total: i64 = slopes
+/// Parses the puzzle input.
+pub fn parse(input: &str) -> Result<Puzzle, ParseError> {
+    Ok(Puzzle {
+        map: parse_map(input)?,
+    })
+}
+
+/// Trees encountered on the slope right 3, down 1.
+pub fn part_one(puzzle: &Puzzle) -> i32 {
+    count_trees_from_slope(&puzzle.map, (3, 1))
+}
+
+/// Product of the trees encountered on the five slopes of the puzzle.
+pub fn part_two(puzzle: &Puzzle) -> i64 {
+    let slopes = [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
+    slopes
         .iter()
-        .map(|s| count_trees_from_slope(&map, *s) as i64)
-        .product();
-    println!("{}", total);
+        .map(|s| count_trees_from_slope(&puzzle.map, *s) as i64)
+        .product()
+}
+
+/// Parses the puzzle input and solves both parts.
+pub fn solve(input: &str) -> Result<(i32, i64), ParseError> {
+    let puzzle = parse(input)?;
+    Ok((part_one(&puzzle), part_two(&puzzle)))
 }
 
-fn create_map_from_file() -> Map {
-    let file = File::open(FILEPATH).expect("File not found");
-    let reader = BufReader::new(file);
+impl Solution for Puzzle {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        parse(input)
+    }
 
-    let mut map = Map::new();
-    for line in reader.lines() {
-        map.import_row(line.expect("Unable to read line from file"));
+    fn part_one(&self) -> Answer {
+        part_one(self).into()
+    }
+
+    fn part_two(&self) -> Answer {
+        part_two(self).into()
     }
-    map
 }
 
-fn count_trees_from_slope(map: &Map, slope: (usize, usize)) -> i32 {
+// The map repeats to the right, so `x` wraps around its width.
+fn count_trees_from_slope(map: &Grid<Square>, slope: (usize, usize)) -> i32 {
     let mut count = 0;
-    for y in 1..map.height / slope.1 {
-        if let Square::Tree = map.get(y * slope.0, y * slope.1) {
+    for y in 1..map.height() / slope.1 {
+        if let Square::Tree = map.get(y * slope.0 % map.width(), y * slope.1) {
             count += 1;
         }
     }
@@ -46,61 +68,51 @@ enum Square {
     Tree,
 }
 
-struct Map {
-    height: usize,
-    width: usize,
-    grid: Vec<Vec<Square>>,
-}
-
-impl Map {
-    fn new() -> Map {
-        Map {
-            height: 0,
-            width: 0,
-            grid: vec![],
-        }
-    }
-
-    fn parse_row(row: String) -> Vec<Square> {
-        row.chars()
-            .map(|c| match c {
-                '#' => Square::Tree,
-                _ => Square::Open,
-            })
-            .collect()
-    }
-
-    fn import_row(&mut self, row: String) {
-        self.height += 1;
-        self.width = row.len();
-        self.grid.push(Map::parse_row(row));
+/*
+ * ..##.......
+ * #...#...#..
+ */
+fn parse_map(input: &str) -> Result<Grid<Square>, ParseError> {
+    let square = |c| match c {
+        '#' => Some(Square::Tree),
+        '.' => Some(Square::Open),
+        _ => None,
+    };
+    let map = Grid::parse(input, square, "`.` or `#`")?;
+    if map.width() == 0 {
+        let line = input.lines().next().unwrap_or("");
+        return Err(ParseError::new(line, line, "expected a row of `.` or `#`"));
     }
+    Ok(map)
+}
 
-    fn get(&self, x: usize, y: usize) -> Square {
-        self.grid[y][x % self.width].clone()
-    }
+#[test]
+fn test_parse_map() {
+    let map = parse_map("..#\n#..").unwrap();
+    assert_eq!((3, 2), (map.width(), map.height()));
+    assert_eq!(&Square::Open, map.get(1, 0));
+    assert_eq!(&Square::Tree, map.get(2, 0));
 }
 
 #[test]
-fn test_parse_row() {
-    let row = Map::parse_row(String::from("..#"));
-    assert_eq!(Square::Open, row[0]);
-    assert_eq!(Square::Open, row[1]);
-    assert_eq!(Square::Tree, row[2]);
+fn test_count_trees_wraps() {
+    let map = parse_map(".#\n.#").unwrap();
+    assert_eq!(1, count_trees_from_slope(&map, (3, 1)));
 }
 
 #[test]
-fn test_map_import_row() {
-    let mut map = Map::new();
-    map.import_row(String::from(".#"));
-    assert_eq!(1, map.height);
-    assert_eq!(2, map.width);
+fn test_parse_map_invalid_square() {
+    let err = parse_map("..O#").unwrap_err();
+    assert_eq!(3, err.column);
 }
 
 #[test]
-fn test_get_square() {
-    let mut map = Map::new();
-    map.import_row(String::from(".#"));
-    map.import_row(String::from(".#"));
-    assert_eq!(Square::Tree, map.get(3, 1));
+fn test_parse_map_invalid_rows() {
+    let err = parse_map("..#\n.#").unwrap_err();
+    assert_eq!(
+        (2, "expected a row of 3 cells"),
+        (err.line, err.message.as_str())
+    );
+    assert!(parse_map("\n\n").is_err());
+    assert!(parse_map("").is_err());
 }
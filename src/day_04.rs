@@ -1,36 +1,75 @@
+//! Day 4: Passport Processing
+
 use std::collections::HashSet;
-use std::fs::File;
-use std::io::{prelude::*, BufReader};
 
 use lazy_static::lazy_static;
 use regex::Regex;
 
-const FILEPATH: &str = "data/04/input.txt";
+use crate::error::{parse_field, ParseError};
+use crate::solution::{Answer, Solution};
+
 lazy_static! {
     static ref RE: Regex = Regex::new(r"^#[a-f0-9]{6}$").expect("Invalid regex");
 }
 
-pub fn first_solution() {
-    let passports = parse_passports();
-    println!(
-        "Solution: {}",
-        passports.iter().filter(|p| p.first_is_valid()).count()
-    );
+/// The batch of passports.
+#[derive(Debug)]
+pub struct Puzzle {
+    passports: Vec<Passport>,
+}
+
+/// Parses the puzzle input.
+pub fn parse(input: &str) -> Result<Puzzle, ParseError> {
+    Ok(Puzzle {
+        passports: parse_passports(input)?,
+    })
+}
+
+/// Number of passports with every required field.
+pub fn part_one(puzzle: &Puzzle) -> usize {
+    puzzle
+        .passports
+        .iter()
+        .filter(|p| p.first_is_valid())
+        .count()
+}
+
+/// Number of passports with every required field holding a valid value.
+pub fn part_two(puzzle: &Puzzle) -> usize {
+    puzzle
+        .passports
+        .iter()
+        .filter(|p| p.second_is_valid())
+        .count()
 }
 
-pub fn second_solution() {
-    let passports = parse_passports();
-    println!(
-        "Solution: {}",
-        passports.iter().filter(|p| p.second_is_valid()).count()
-    );
+/// Parses the puzzle input and solves both parts.
+pub fn solve(input: &str) -> Result<(usize, usize), ParseError> {
+    let puzzle = parse(input)?;
+    Ok((part_one(&puzzle), part_two(&puzzle)))
 }
 
+impl Solution for Puzzle {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        parse(input)
+    }
+
+    fn part_one(&self) -> Answer {
+        part_one(self).into()
+    }
+
+    fn part_two(&self) -> Answer {
+        part_two(self).into()
+    }
+}
+
+#[derive(Debug)]
 struct Height {
     value: i32,
     unit: String,
 }
 
+#[derive(Debug)]
 struct Passport {
     byr: Option<i32>,    // (Birth Year)
     iyr: Option<i32>,    // (Issue Year)
@@ -123,46 +162,75 @@ impl Passport {
  * ecl:gry pid:860033327 eyr:2020 hcl:#fffffd
  * byr:1937 iyr:2017 cid:147 hgt:183cm
  */
-fn parse_passports() -> Vec<Passport> {
-    let file = File::open(FILEPATH).expect("File not found");
-    let reader = BufReader::new(file);
-
+fn parse_passports(input: &str) -> Result<Vec<Passport>, ParseError> {
     let mut passports = vec![];
     let mut passport = Passport::new();
-    for line in reader
-        .lines()
-        .map(|l| l.expect("Unable to read line from file"))
-    {
+    for (i, line) in input.lines().enumerate() {
         if line.is_empty() {
             passports.push(passport);
             passport = Passport::new();
             continue;
         }
-        for block in line.split_whitespace().map(|t| t.trim()) {
-            let token: Vec<&str> = block.split(':').collect();
-            match token[0] {
-                "byr" => passport.byr = Some(token[1].parse::<i32>().expect("numeric value")),
-                "iyr" => passport.iyr = Some(token[1].parse::<i32>().expect("numeric value")),
-                "eyr" => passport.eyr = Some(token[1].parse::<i32>().expect("numeric value")),
-                "cid" => passport.cid = Some(token[1].parse::<i32>().expect("numeric value")),
-                "pid" => passport.pid = Some(token[1].to_string()),
-                "hgt" => {
-                    let (mut value, mut unit) = token[1].split_at(token[1].len() - 2);
-                    if !token[1].ends_with("cm") && !token[1].ends_with("in") {
-                        value = token[1];
-                        unit = "";
-                    }
-                    passport.hgt = Some(Height {
-                        value: value.parse::<i32>().expect("numeric value"),
-                        unit: unit.to_string(),
-                    })
-                }
-                "hcl" => passport.hcl = Some(token[1].to_string()),
-                "ecl" => passport.ecl = Some(token[1].to_string()),
-                _ => panic!("Unexpected value"),
-            }
+        for block in line.split_whitespace() {
+            parse_passport_field(&mut passport, line, block).map_err(|e| e.offset(i))?;
         }
     }
     passports.push(passport);
-    passports
+    Ok(passports)
+}
+
+fn parse_passport_field(
+    passport: &mut Passport,
+    line: &str,
+    block: &str,
+) -> Result<(), ParseError> {
+    let (key, value) = block
+        .split_once(':')
+        .ok_or_else(|| ParseError::new(line, block, "expected `<field>:<value>`"))?;
+    match key {
+        "byr" => passport.byr = Some(parse_field(line, value, "a year")?),
+        "iyr" => passport.iyr = Some(parse_field(line, value, "a year")?),
+        "eyr" => passport.eyr = Some(parse_field(line, value, "a year")?),
+        "cid" => passport.cid = Some(parse_field(line, value, "a number")?),
+        "pid" => passport.pid = Some(value.to_string()),
+        "hgt" => {
+            let (value, unit) = if let Some(v) = value.strip_suffix("cm") {
+                (v, "cm")
+            } else if let Some(v) = value.strip_suffix("in") {
+                (v, "in")
+            } else {
+                (value, "")
+            };
+            passport.hgt = Some(Height {
+                value: parse_field(line, value, "a height")?,
+                unit: unit.to_string(),
+            })
+        }
+        "hcl" => passport.hcl = Some(value.to_string()),
+        "ecl" => passport.ecl = Some(value.to_string()),
+        _ => {
+            return Err(ParseError::new(
+                line,
+                key,
+                format!("unexpected field `{}`", key),
+            ))
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_parse_passports() {
+    let input = "ecl:gry pid:860033327 eyr:2020 hcl:#fffffd\nbyr:1937 iyr:2017 cid:147 hgt:183cm\n\niyr:2013 ecl:amb";
+    let passports = parse_passports(input).unwrap();
+    assert_eq!(2, passports.len());
+    assert!(passports[0].second_is_valid());
+    assert!(!passports[1].first_is_valid());
+}
+
+#[test]
+fn test_parse_passports_unexpected_field() {
+    let err = parse_passports("ecl:gry\npid:860033327 foo:bar").unwrap_err();
+    assert_eq!(2, err.line);
+    assert_eq!(15, err.column);
 }
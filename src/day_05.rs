@@ -1,51 +1,93 @@
+//! Day 5: Binary Boarding
+
 use std::collections::HashSet;
-use std::fs::File;
-use std::io::{prelude::*, BufReader};
-
-const FILEPATH: &str = "data/05/input.txt";
-
-pub fn first_solution() {
-    let file = File::open(FILEPATH).expect("File not found");
-    let reader = BufReader::new(file);
-    let max = reader
-        .lines()
-        .map(|bp| seat_id(bp.expect("unable to parse line")))
-        .max();
-    println!("Solution: {}", max.expect("unable to get max value"));
-}
-
-pub fn second_solution() {
-    let file = File::open(FILEPATH).expect("File not found");
-    let reader = BufReader::new(file);
-    let seats: HashSet<i32> = reader
-        .lines()
-        .map(|bp| seat_id(bp.expect("unable to parse line")))
-        .collect();
+
+use crate::error::{parse_lines, ParseError};
+use crate::solution::{Answer, Solution};
+
+/// The seat ids of the scanned boarding passes.
+#[derive(Debug)]
+pub struct Puzzle {
+    seats: HashSet<i32>,
+}
+
+/// Parses the puzzle input.
+pub fn parse(input: &str) -> Result<Puzzle, ParseError> {
+    Ok(Puzzle {
+        seats: parse_lines(input, seat_id)?.into_iter().collect(),
+    })
+}
+
+/// Highest seat id, if there is any boarding pass.
+pub fn part_one(puzzle: &Puzzle) -> Option<i32> {
+    puzzle.seats.iter().max().copied()
+}
+
+/// Id of the only missing seat whose neighbours are both taken.
+pub fn part_two(puzzle: &Puzzle) -> Option<i32> {
     // max seat
-    for i in 1..127 * 8 + 7 {
-        if !seats.contains(&i) && seats.contains(&(i - 1)) && seats.contains(&(i + 1)) {
-            println!("Solution: {}", i);
-            break;
-        }
+    (1..127 * 8 + 7).find(|i| {
+        !puzzle.seats.contains(i)
+            && puzzle.seats.contains(&(i - 1))
+            && puzzle.seats.contains(&(i + 1))
+    })
+}
+
+/// Parses the puzzle input and solves both parts.
+pub fn solve(input: &str) -> Result<(Option<i32>, Option<i32>), ParseError> {
+    let puzzle = parse(input)?;
+    Ok((part_one(&puzzle), part_two(&puzzle)))
+}
+
+impl Solution for Puzzle {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        parse(input)
+    }
+
+    fn part_one(&self) -> Answer {
+        part_one(self).into()
+    }
+
+    fn part_two(&self) -> Answer {
+        part_two(self).into()
     }
 }
 
-fn seat_id(boarding_pass: String) -> i32 {
-    i32::from_str_radix(
-        boarding_pass
-            .replace("F", "0")
-            .replace("B", "1")
-            .replace("L", "0")
-            .replace("R", "1")
-            .as_str(),
-        2,
-    )
-    .expect("invalid boarding pass")
+fn seat_id(boarding_pass: &str) -> Result<i32, ParseError> {
+    if boarding_pass.len() != 10 {
+        return Err(ParseError::new(
+            boarding_pass,
+            boarding_pass,
+            "expected a boarding pass of 10 characters",
+        ));
+    }
+    let mut id = 0;
+    for (i, ch) in boarding_pass.char_indices() {
+        let bit = match (i, ch) {
+            (0..=6, 'F') | (7..=9, 'L') => 0,
+            (0..=6, 'B') | (7..=9, 'R') => 1,
+            _ => {
+                return Err(ParseError::new(
+                    boarding_pass,
+                    &boarding_pass[i..],
+                    format!("unexpected `{}` in boarding pass", ch),
+                ))
+            }
+        };
+        id = (id << 1) | bit;
+    }
+    Ok(id)
 }
 
 #[test]
 fn test_seat_ids() {
-    assert_eq!(567, seat_id("BFFFBBFRRR".to_string()));
-    assert_eq!(119, seat_id("FFFBBBFRRR".to_string()));
-    assert_eq!(820, seat_id("BBFFBBFRLL".to_string()));
+    assert_eq!(567, seat_id("BFFFBBFRRR").unwrap());
+    assert_eq!(119, seat_id("FFFBBBFRRR").unwrap());
+    assert_eq!(820, seat_id("BBFFBBFRLL").unwrap());
+}
+
+#[test]
+fn test_seat_id_invalid() {
+    assert_eq!(8, seat_id("BFFFBBFFRR").unwrap_err().column);
+    assert!(seat_id("BFFFBBF").is_err());
 }
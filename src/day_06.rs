@@ -1,16 +1,69 @@
+//! Day 6: Custom Customs
+
 use std::collections::{HashMap, HashSet};
-use std::fs::File;
-use std::io::{prelude::*, BufReader};
 
-const FILEPATH: &str = "data/06/input.txt";
+use crate::error::{parse_lines, ParseError};
+use crate::solution::{Answer, Solution};
+
+/// The answers of every person, groups separated by empty lines.
+#[derive(Debug)]
+pub struct Puzzle {
+    lines: Vec<String>,
+}
+
+/// Parses the puzzle input.
+pub fn parse(input: &str) -> Result<Puzzle, ParseError> {
+    Ok(Puzzle {
+        lines: parse_lines(input, parse_answers)?,
+    })
+}
+
+/// Sum over groups of the questions anyone answered "yes" to.
+pub fn part_one(puzzle: &Puzzle) -> i32 {
+    count_anyone_answered(&puzzle.lines)
+}
+
+/// Sum over groups of the questions everyone answered "yes" to.
+pub fn part_two(puzzle: &Puzzle) -> i32 {
+    count_everyone_answered(&puzzle.lines)
+}
+
+/// Parses the puzzle input and solves both parts.
+pub fn solve(input: &str) -> Result<(i32, i32), ParseError> {
+    let puzzle = parse(input)?;
+    Ok((part_one(&puzzle), part_two(&puzzle)))
+}
+
+impl Solution for Puzzle {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        parse(input)
+    }
+
+    fn part_one(&self) -> Answer {
+        part_one(self).into()
+    }
+
+    fn part_two(&self) -> Answer {
+        part_two(self).into()
+    }
+}
 
-pub fn first_solution() {
-    let file = File::open(FILEPATH).unwrap();
-    let reader = BufReader::new(file);
+// One person's answers: the questions, `a` to `z`, they answered "yes" to.
+fn parse_answers(line: &str) -> Result<String, ParseError> {
+    match line.char_indices().find(|(_, ch)| !ch.is_ascii_lowercase()) {
+        Some((i, ch)) => Err(ParseError::new(
+            line,
+            &line[i..],
+            format!("expected a question from `a` to `z`, found `{}`", ch),
+        )),
+        None => Ok(line.to_string()),
+    }
+}
 
+fn count_anyone_answered(lines: &[String]) -> i32 {
     let mut group: HashSet<char> = HashSet::new();
     let mut count: i32 = 0;
-    for line in reader.lines().map(|l| l.expect("Unable to read line")) {
+    for line in lines.iter() {
         if line.is_empty() {
             count += group.len() as i32;
             group = HashSet::new();
@@ -21,17 +74,14 @@ pub fn first_solution() {
         }
     }
     count += group.len() as i32;
-    println!("Solution: {}", count);
+    count
 }
 
-pub fn second_solution() {
-    let file = File::open(FILEPATH).unwrap();
-    let reader = BufReader::new(file);
-
+fn count_everyone_answered(lines: &[String]) -> i32 {
     let mut group: HashMap<char, usize> = HashMap::new();
     let mut num_in_group: usize = 0;
     let mut count: i32 = 0;
-    for line in reader.lines().map(|l| l.expect("Unable to read line")) {
+    for line in lines.iter() {
         if line.is_empty() {
             count += group.values().filter(|v| **v == num_in_group).count() as i32;
             group = HashMap::new();
@@ -51,6 +101,17 @@ pub fn second_solution() {
         }
     }
     count += group.values().filter(|v| **v == num_in_group).count() as i32;
+    count
+}
+
+#[test]
+fn test_count_answered() {
+    let lines = parse_lines("abc\n\na\nb\nc\n\nab\nac\n\na\na\na\na\n\nb", parse_answers).unwrap();
+    assert_eq!(11, count_anyone_answered(&lines));
+    assert_eq!(6, count_everyone_answered(&lines));
+}
 
-    println!("Solution: {}", count);
+#[test]
+fn test_parse_answers_invalid() {
+    assert_eq!(3, parse_answers("ab?").unwrap_err().column);
 }
@@ -1,39 +1,62 @@
+//! Day 7: Handy Haversacks
+
 use std::collections::HashMap;
-use std::fs::File;
-use std::io::{prelude::*, BufReader};
 
 use lazy_static::lazy_static;
 use regex::Regex;
 
-const FILEPATH: &str = "data/07/input.txt";
+use crate::error::{parse_field, ParseError};
+use crate::solution::{Answer, Solution};
 
-pub fn first_solution() {
-    let file = File::open(FILEPATH).unwrap();
-    let reader = BufReader::new(file);
-    let graph = create_graph(
-        reader
-            .lines()
-            .map(|l| l.expect("Unable to read line"))
-            .collect(),
-    );
-    let total = graph
+/// The bag rules, as a graph of bag colours.
+#[derive(Debug)]
+pub struct Puzzle {
+    graph: BagGraph,
+}
+
+/// Parses the puzzle input.
+pub fn parse(input: &str) -> Result<Puzzle, ParseError> {
+    let lines: Vec<String> = input.lines().map(|l| l.to_string()).collect();
+    let count = lines.len();
+    let graph = create_graph(lines)?;
+    if !graph.contains_key("shiny gold") {
+        return Err(ParseError::new("", "", "expected a rule for shiny gold bags").offset(count));
+    }
+    Ok(Puzzle { graph })
+}
+
+/// Number of bag colours that eventually contain a shiny gold bag.
+pub fn part_one(puzzle: &Puzzle) -> usize {
+    puzzle
+        .graph
         .keys()
-        .filter(|k| holds_shiny_gold_bag(&graph, k.to_string()))
-        .count();
-    println!("Solution: {}", total);
+        .filter(|k| holds_shiny_gold_bag(&puzzle.graph, k.to_string()))
+        .count()
 }
 
-pub fn second_solution() {
-    let file = File::open(FILEPATH).unwrap();
-    let reader = BufReader::new(file);
-    let graph = create_graph(
-        reader
-            .lines()
-            .map(|l| l.expect("Unable to read line"))
-            .collect(),
-    );
-    let total = count_individual_bags(&graph, "shiny gold".to_string()) - 1;
-    println!("Solution: {}", total);
+/// Number of bags required inside a shiny gold bag.
+pub fn part_two(puzzle: &Puzzle) -> u32 {
+    count_individual_bags(&puzzle.graph, "shiny gold".to_string()) - 1
+}
+
+/// Parses the puzzle input and solves both parts.
+pub fn solve(input: &str) -> Result<(usize, u32), ParseError> {
+    let puzzle = parse(input)?;
+    Ok((part_one(&puzzle), part_two(&puzzle)))
+}
+
+impl Solution for Puzzle {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        parse(input)
+    }
+
+    fn part_one(&self) -> Answer {
+        part_one(self).into()
+    }
+
+    fn part_two(&self) -> Answer {
+        part_two(self).into()
+    }
 }
 
 lazy_static! {
@@ -42,39 +65,46 @@ lazy_static! {
         Regex::new(r"(\d+|no)\s(\w+\s?\w+?)\sbags?").expect("Invalid regex");
 }
 
-type BagGraph = HashMap<String, Vec<(usize, String)>>;
+/// Bag colour to the number and colour of the bags it must contain.
+pub type BagGraph = HashMap<String, Vec<(usize, String)>>;
 
-fn create_graph(lines: Vec<String>) -> BagGraph {
+/// Builds the bag graph from rules like
+/// `light red bags contain 1 bright white bag, 2 muted yellow bags.`
+pub fn create_graph(lines: Vec<String>) -> Result<BagGraph, ParseError> {
     let mut graph: BagGraph = BagGraph::new();
-    for line in lines.iter() {
-        let split = RE_SPLIT.captures(line).expect("Invalid Bag rule");
-        for group in RE_GROUPS.captures_iter(&split[2]) {
-            let num = (&group[1]).to_string().parse::<usize>().unwrap_or(0);
-            match graph.get_mut(&split[1].to_string()) {
-                Some(val) => {
-                    val.push((num, (&group[2]).to_string()));
-                }
-                _ => {
-                    let bags = if num == 0 {
-                        vec![]
-                    } else {
-                        vec![(num, (&group[2]).to_string())]
-                    };
-                    graph.insert((&split[1]).to_string(), bags);
-                }
+    let mut references = vec![];
+    for (i, line) in lines.iter().enumerate() {
+        let split = RE_SPLIT.captures(line).ok_or_else(|| {
+            ParseError::new(line, line, "expected `<colour> bags contain <bags>.`").offset(i)
+        })?;
+        let contents = split.get(2).expect("expected capture group").as_str();
+        let bags = graph.entry(split[1].to_string()).or_default();
+        for group in RE_GROUPS.captures_iter(contents) {
+            if &group[1] == "no" {
+                continue;
             }
+            let num = parse_field(line, &group[1], "a number").map_err(|e| e.offset(i))?;
+            let colour = group.get(2).expect("expected capture group").as_str();
+            references.push((i, line, colour));
+            bags.push((num, colour.to_string()));
+        }
+    }
+    for (i, line, colour) in references {
+        if !graph.contains_key(colour) {
+            let message = format!("unknown bag colour `{}`", colour);
+            return Err(ParseError::new(line, colour, message).offset(i));
         }
     }
 
-    graph
+    Ok(graph)
 }
 
 fn holds_shiny_gold_bag(graph: &BagGraph, bag: String) -> bool {
     for (_, b) in graph[&bag].iter() {
-        if b == &"shiny gold".to_string() {
+        if b == "shiny gold" {
             return true;
         }
-        if holds_shiny_gold_bag(&graph, b.clone()) {
+        if holds_shiny_gold_bag(graph, b.clone()) {
             return true;
         }
     }
@@ -86,7 +116,7 @@ fn count_individual_bags(graph: &BagGraph, bag: String) -> u32 {
         .get(&bag)
         .unwrap()
         .iter()
-        .map(|(n, b)| count_individual_bags(&graph, b.clone()) * (*n) as u32)
+        .map(|(n, b)| count_individual_bags(graph, b.clone()) * (*n) as u32)
         .sum::<u32>()
         + 1
 }
@@ -97,15 +127,16 @@ fn test_create_graph() {
         "light red bags contain 1 bright white bag, 2 muted yellow bags.".to_string(),
         "bright white bags contain 1 shiny gold bag.".to_string(),
         "muted yellow bags contain no other bags.".to_string(),
+        "shiny gold bags contain no other bags.".to_string(),
     ];
-    let graph = create_graph(lines);
+    let graph = create_graph(lines).unwrap();
     println!("{:?}", graph);
-    assert_eq!(3, graph.len());
+    assert_eq!(4, graph.len());
     let light_red: Vec<(usize, String)> = vec![
         (1, "bright white".to_string()),
         (2, "muted yellow".to_string()),
     ];
-    assert_eq!(light_red, *graph.get(&"light red".to_string()).unwrap());
+    assert_eq!(light_red, *graph.get("light red").unwrap());
 }
 
 #[test]
@@ -121,8 +152,8 @@ fn test_contain_shiny_gold_bags() {
         "faded blue bags contain no other bags.".to_string(),
         "dotted black bags contain no other bags.".to_string(),
     ];
-    let graph = create_graph(lines);
-    assert_eq!(true, holds_shiny_gold_bag(&graph, "light red".to_string()));
+    let graph = create_graph(lines).unwrap();
+    assert!(holds_shiny_gold_bag(&graph, "light red".to_string()));
     assert_eq!(
         4,
         graph
@@ -145,9 +176,36 @@ fn test_count_individual_bags() {
         "faded blue bags contain no other bags.".to_string(),
         "dotted black bags contain no other bags.".to_string(),
     ];
-    let graph = create_graph(lines);
+    let graph = create_graph(lines).unwrap();
     assert_eq!(
         33, // shiny gold is counted here
         count_individual_bags(&graph, "shiny gold".to_string())
     );
 }
+
+#[test]
+fn test_create_graph_invalid_rule() {
+    let lines: Vec<String> = vec![
+        "light red bags contain 1 bright white bag.".to_string(),
+        "bright white bags hold 1 shiny gold bag.".to_string(),
+    ];
+    assert_eq!(2, create_graph(lines).unwrap_err().line);
+}
+
+#[test]
+fn test_create_graph_invalid_bags() {
+    let lines: Vec<String> = vec![
+        "light red bags contain 99999999999999999999 bright white bags.".to_string(),
+        "bright white bags contain no other bags.".to_string(),
+    ];
+    let err = create_graph(lines).unwrap_err();
+    assert_eq!((1, 24), (err.line, err.column));
+    let lines: Vec<String> = vec![
+        "bright white bags contain no other bags.".to_string(),
+        "light red bags contain 1 bright white bag, 2 muted yellow bags.".to_string(),
+    ];
+    let err = create_graph(lines).unwrap_err();
+    assert_eq!((2, 46), (err.line, err.column));
+    assert_eq!("unknown bag colour `muted yellow`", err.message);
+    assert!(parse("").is_err());
+}
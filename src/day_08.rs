@@ -1,63 +1,86 @@
+//! Day 8: Handheld Halting
+
 use std::collections::HashSet;
-use std::fs::File;
-use std::io::{prelude::*, BufReader};
 
 use lazy_static::lazy_static;
 use regex::Regex;
 
-const FILEPATH: &str = "data/08/input.txt";
+use crate::error::{parse_field, parse_lines, ParseError};
+use crate::solution::{Answer, Solution};
+
+/// The boot code instructions.
+#[derive(Debug)]
+pub struct Puzzle {
+    instructions: Vec<Instruction>,
+}
 
-pub fn first_solution() {
-    let instructions = parse_input();
-    let total = match execute_instructions(&instructions) {
+/// Parses the puzzle input.
+pub fn parse(input: &str) -> Result<Puzzle, ParseError> {
+    Ok(Puzzle {
+        instructions: parse_lines(input, parse_instruction)?,
+    })
+}
+
+/// Accumulator value right before any instruction runs a second time.
+pub fn part_one(puzzle: &Puzzle) -> i64 {
+    match execute_instructions(&puzzle.instructions) {
         Ok(value) => value,
         Err(value) => value,
-    };
-    println!("Solution: {}", total);
+    }
 }
 
-pub fn second_solution() {
-    let mut instructions = parse_input();
+/// Accumulator value once the program terminates, after fixing the one corrupted instruction.
+pub fn part_two(puzzle: &Puzzle) -> Option<i64> {
+    let mut instructions = puzzle.instructions.clone();
     for i in 0..instructions.len() {
-        if instructions[i].operation == Operation::ACC {
+        if instructions[i].operation == Operation::Acc {
             continue;
         }
         instructions[i] = switch_instruction(&instructions[i]);
         match execute_instructions(&instructions) {
-            Ok(value) => {
-                println!("Solution: {}", value);
-                break;
-            }
+            Ok(value) => return Some(value),
             Err(_) => {
                 // Switch back
                 instructions[i] = switch_instruction(&instructions[i]);
             }
         }
     }
+    None
+}
+
+/// Parses the puzzle input and solves both parts.
+pub fn solve(input: &str) -> Result<(i64, Option<i64>), ParseError> {
+    let puzzle = parse(input)?;
+    Ok((part_one(&puzzle), part_two(&puzzle)))
 }
 
-#[derive(Debug, PartialEq)]
+impl Solution for Puzzle {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        parse(input)
+    }
+
+    fn part_one(&self) -> Answer {
+        part_one(self).into()
+    }
+
+    fn part_two(&self) -> Answer {
+        part_two(self).into()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
 enum Operation {
-    NOP,
-    ACC,
-    JMP,
+    Nop,
+    Acc,
+    Jmp,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 struct Instruction {
     operation: Operation,
     value: i32,
 }
 
-fn parse_input() -> Vec<Instruction> {
-    let file = File::open(FILEPATH).unwrap();
-    let reader = BufReader::new(file);
-    reader
-        .lines()
-        .map(|v| parse_instruction(v.expect("Unable to read line")))
-        .collect()
-}
-
 fn execute_instructions(instructions: &[Instruction]) -> Result<i64, i64> {
     let mut executed = HashSet::new();
     let mut pointer: i32 = 0;
@@ -66,12 +89,12 @@ fn execute_instructions(instructions: &[Instruction]) -> Result<i64, i64> {
         executed.insert(pointer);
         let instruction = &instructions[pointer as usize];
         match instruction.operation {
-            Operation::NOP => pointer += 1,
-            Operation::ACC => {
+            Operation::Nop => pointer += 1,
+            Operation::Acc => {
                 acc += instruction.value as i64;
                 pointer += 1
             }
-            Operation::JMP => pointer += instruction.value,
+            Operation::Jmp => pointer += instruction.value,
         };
     }
     if !executed.contains(&pointer) {
@@ -84,9 +107,9 @@ fn execute_instructions(instructions: &[Instruction]) -> Result<i64, i64> {
 fn switch_instruction(instruction: &Instruction) -> Instruction {
     Instruction {
         operation: match instruction.operation {
-            Operation::NOP => Operation::JMP,
-            Operation::JMP => Operation::NOP,
-            Operation::ACC => Operation::ACC,
+            Operation::Nop => Operation::Jmp,
+            Operation::Jmp => Operation::Nop,
+            Operation::Acc => Operation::Acc,
         },
         value: instruction.value,
     }
@@ -96,68 +119,84 @@ lazy_static! {
     static ref RE: Regex = Regex::new(r"^(\w+)\s(.)(\d+)").expect("Invalid regex");
 }
 
-fn parse_instruction(instruction: String) -> Instruction {
-    let res = RE
-        .captures(instruction.as_str())
-        .expect("Invalid instruction");
-    let value = res[3].parse::<i32>().expect("Expected a numeric value");
-    Instruction {
+fn parse_instruction(instruction: &str) -> Result<Instruction, ParseError> {
+    let res = RE.captures(instruction).ok_or_else(|| {
+        ParseError::new(
+            instruction,
+            instruction,
+            "expected `<operation> <+/-><value>`",
+        )
+    })?;
+    let value = parse_field::<i32>(instruction, &res[3], "a number")?;
+    Ok(Instruction {
         operation: match &res[1] {
-            "jmp" => Operation::JMP,
-            "nop" => Operation::NOP,
-            "acc" => Operation::ACC,
-            op => panic!("Invalid operation: {}", op),
+            "jmp" => Operation::Jmp,
+            "nop" => Operation::Nop,
+            "acc" => Operation::Acc,
+            op => {
+                return Err(ParseError::new(
+                    instruction,
+                    &res[1],
+                    format!("invalid operation `{}`", op),
+                ))
+            }
         },
         value: value
             * match &res[2] {
                 "-" => -1,
                 "+" => 1,
-                _ => panic!("Expected +/-"),
+                sign => {
+                    return Err(ParseError::new(
+                        instruction,
+                        &res[2],
+                        format!("expected `+` or `-`, found `{}`", sign),
+                    ))
+                }
             },
-    }
+    })
 }
 
 #[test]
 fn test_execute_instructions_until_loop() {
     let instructions = vec![
         Instruction {
-            operation: Operation::NOP,
+            operation: Operation::Nop,
             value: 0,
         },
         Instruction {
-            operation: Operation::ACC,
+            operation: Operation::Acc,
             value: 1,
         },
         Instruction {
-            operation: Operation::JMP,
+            operation: Operation::Jmp,
             value: 4,
         },
         Instruction {
-            operation: Operation::ACC,
+            operation: Operation::Acc,
             value: 3,
         },
         Instruction {
-            operation: Operation::JMP,
+            operation: Operation::Jmp,
             value: -3,
         },
         Instruction {
-            operation: Operation::ACC,
+            operation: Operation::Acc,
             value: -99,
         },
         Instruction {
-            operation: Operation::ACC,
+            operation: Operation::Acc,
             value: 1,
         },
         Instruction {
-            operation: Operation::JMP,
+            operation: Operation::Jmp,
             value: -4,
         },
         Instruction {
-            operation: Operation::ACC,
+            operation: Operation::Acc,
             value: 6,
         },
     ];
-    assert_eq!(true, execute_instructions(&instructions).is_err());
+    assert!(execute_instructions(&instructions).is_err());
     assert_eq!(
         5,
         match execute_instructions(&instructions) {
@@ -170,8 +209,14 @@ fn test_execute_instructions_until_loop() {
 #[test]
 fn test_parse_instruction() {
     let expected = Instruction {
-        operation: Operation::JMP,
+        operation: Operation::Jmp,
         value: -99,
     };
-    assert_eq!(expected, parse_instruction("jmp -99".to_string()));
+    assert_eq!(expected, parse_instruction("jmp -99").unwrap());
+}
+
+#[test]
+fn test_parse_instruction_invalid() {
+    assert_eq!(1, parse_instruction("mov +1").unwrap_err().column);
+    assert_eq!(5, parse_instruction("acc *1").unwrap_err().column);
 }
@@ -1,32 +1,52 @@
+//! Day 9: Encoding Error
+
 use itertools::Itertools;
-use std::fs::File;
-use std::io::{prelude::*, BufReader};
 
-const FILEPATH: &str = "data/09/input.txt";
+use crate::error::{parse_field, parse_lines, ParseError};
+use crate::solution::{Answer, Solution};
+
+/// The XMAS-encrypted numbers.
+#[derive(Debug)]
+pub struct Puzzle {
+    numbers: Vec<u64>,
+}
+
+/// Parses the puzzle input.
+pub fn parse(input: &str) -> Result<Puzzle, ParseError> {
+    Ok(Puzzle {
+        numbers: parse_lines(input, |w| parse_field(w, w, "a number"))?,
+    })
+}
+
+/// First number that is not the sum of two of the 25 numbers before it.
+pub fn part_one(puzzle: &Puzzle) -> Option<u64> {
+    find_wrong_number(25, puzzle.numbers.clone())
+}
 
-pub fn first_solution() {
-    let file = File::open(FILEPATH).unwrap();
-    let reader = BufReader::new(file);
+/// Sum of the smallest and largest numbers of the contiguous range adding up to the part one number.
+pub fn part_two(puzzle: &Puzzle) -> Option<u64> {
+    let invalid_number = part_one(puzzle)?;
+    find_contiguous_numbers(invalid_number, puzzle.numbers.clone())
+}
 
-    let numbers = reader
-        .lines()
-        .map(|w| w.unwrap().parse::<u64>().expect("expect numeric values"))
-        .collect();
-    println!("Solution: {}", find_wrong_number(25, numbers).unwrap());
+/// Parses the puzzle input and solves both parts.
+pub fn solve(input: &str) -> Result<(Option<u64>, Option<u64>), ParseError> {
+    let puzzle = parse(input)?;
+    Ok((part_one(&puzzle), part_two(&puzzle)))
 }
 
-pub fn second_solution() {
-    let file = File::open(FILEPATH).unwrap();
-    let reader = BufReader::new(file);
+impl Solution for Puzzle {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        parse(input)
+    }
+
+    fn part_one(&self) -> Answer {
+        part_one(self).into()
+    }
 
-    let numbers = reader
-        .lines()
-        .map(|w| w.unwrap().parse::<u64>().expect("expect numeric values"))
-        .collect();
-    println!(
-        "Solution: {}",
-        find_contiguous_numbers(23278925, numbers).unwrap()
-    );
+    fn part_two(&self) -> Answer {
+        part_two(self).into()
+    }
 }
 
 fn find_wrong_number(window_size: usize, values: Vec<u64>) -> Option<u64> {
@@ -48,7 +68,7 @@ fn find_wrong_number(window_size: usize, values: Vec<u64>) -> Option<u64> {
 fn find_contiguous_numbers(invalid_number: u64, values: Vec<u64>) -> Option<u64> {
     for comb_length in 2..values.len() {
         for combination in values.windows(comb_length) {
-            if invalid_number == combination.iter().copied().sum() {
+            if invalid_number == combination.iter().copied().sum::<u64>() {
                 return Some(
                     combination.iter().min().expect("unexpected empty values")
                         + combination.iter().max().expect("unexpected empty values"),
@@ -1,35 +1,80 @@
-use std::collections::HashMap;
-use std::fs::File;
-use std::io::{prelude::*, BufReader};
+//! Day 10: Adapter Array
 
-const FILEPATH: &str = "data/10/input.txt";
+use std::collections::{HashMap, HashSet};
 
-pub fn first_solution() {
-    let numbers = get_sorted_input();
-    let distribution = get_jolt_distribution(numbers);
-    println!("Solution: {}", distribution[0] * distribution[2]);
+use crate::error::{parse_field, ParseError};
+use crate::solution::{Answer, Solution};
+
+/// The sorted joltages, including the outlet and the device.
+#[derive(Debug)]
+pub struct Puzzle {
+    numbers: Vec<i32>,
+}
+
+/// Parses the puzzle input.
+pub fn parse(input: &str) -> Result<Puzzle, ParseError> {
+    Ok(Puzzle {
+        numbers: get_sorted_input(input)?,
+    })
+}
+
+/// Number of 1-jolt differences multiplied by the number of 3-jolt differences.
+pub fn part_one(puzzle: &Puzzle) -> i32 {
+    let distribution = get_jolt_distribution(puzzle.numbers.clone());
+    distribution[0] * distribution[2]
 }
 
-pub fn second_solution() {
-    let numbers = get_sorted_input();
+/// Number of distinct adapter arrangements that connect the outlet to the device.
+pub fn part_two(puzzle: &Puzzle) -> u64 {
     let mut cache = HashMap::new();
-    println!(
-        "Solution: {}",
-        count_valid_arrangements(&get_adapters(numbers), 0, &mut cache)
-    );
+    count_valid_arrangements(&get_adapters(puzzle.numbers.clone()), 0, &mut cache)
+}
+
+/// Parses the puzzle input and solves both parts.
+pub fn solve(input: &str) -> Result<(i32, u64), ParseError> {
+    let puzzle = parse(input)?;
+    Ok((part_one(&puzzle), part_two(&puzzle)))
+}
+
+impl Solution for Puzzle {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        parse(input)
+    }
+
+    fn part_one(&self) -> Answer {
+        part_one(self).into()
+    }
+
+    fn part_two(&self) -> Answer {
+        part_two(self).into()
+    }
 }
 
-fn get_sorted_input() -> Vec<i32> {
-    let file = File::open(FILEPATH).unwrap();
-    let reader = BufReader::new(file);
-    let mut numbers = reader
-        .lines()
-        .map(|w| w.unwrap().parse::<i32>().expect("expect numeric value"))
-        .collect::<Vec<i32>>();
+// Adapters must have distinct positive joltages, leaving room for the device
+// rated 3 jolts above the highest one.
+fn get_sorted_input(input: &str) -> Result<Vec<i32>, ParseError> {
+    let mut numbers: Vec<i32> = vec![];
+    let mut seen = HashSet::new();
+    for (i, line) in input.lines().enumerate() {
+        let n = parse_field(line, line, "a number").map_err(|e| e.offset(i))?;
+        let message = if !(1..=i32::MAX - 3).contains(&n) {
+            format!("expected a joltage from 1 to {}, found {}", i32::MAX - 3, n)
+        } else if !seen.insert(n) {
+            format!("duplicate adapter {}", n)
+        } else {
+            numbers.push(n);
+            continue;
+        };
+        return Err(ParseError::new(line, line, message).offset(i));
+    }
+    let max = match numbers.iter().max() {
+        Some(max) => *max,
+        None => return Err(ParseError::new("", "", "expected at least one adapter")),
+    };
     numbers.push(0);
-    numbers.push(*numbers.iter().max().expect("unexpected empty input") + 3);
+    numbers.push(max + 3);
     numbers.sort();
-    numbers
+    Ok(numbers)
 }
 
 type Distribution = [i32; 3];
@@ -103,3 +148,12 @@ fn test_get_num_valid_arrangements() {
     let mut cache = HashMap::new();
     assert_eq!(8, count_valid_arrangements(&input, 0, &mut cache));
 }
+
+#[test]
+fn test_get_sorted_input_invalid() {
+    let err = get_sorted_input("1\n4\n1").unwrap_err();
+    assert_eq!((3, "duplicate adapter 1"), (err.line, err.message.as_str()));
+    assert_eq!(2, get_sorted_input("1\n0").unwrap_err().line);
+    assert!(get_sorted_input("-2").is_err());
+    assert_eq!(vec![0, 1, 4, 7], get_sorted_input("4\n1").unwrap());
+}
@@ -1,29 +1,50 @@
-use std::cmp::max;
-use std::fs::File;
-use std::io::{prelude::*, BufReader};
-
-const FILEPATH: &str = "data/11/input.txt";
-
-pub fn first_solution() {
-    let seats = run_simulation(&read_seats_from_file(), &count_adjacent, 4);
-    println!(
-        "{}",
-        seats
-            .iter()
-            .map(|r| r.iter().filter(|s| **s == SeatStatus::Occupied).count())
-            .sum::<usize>()
-    );
+//! Day 11: Seating System
+
+use crate::error::ParseError;
+use crate::grid::Grid;
+use crate::solution::{Answer, Solution};
+
+/// The initial seat layout.
+#[derive(Debug)]
+pub struct Puzzle {
+    seats: SeatLayout,
 }
 
-pub fn second_solution() {
-    let seats = run_simulation(&read_seats_from_file(), &count_visible, 5);
-    println!(
-        "{}",
-        seats
-            .iter()
-            .map(|r| r.iter().filter(|s| **s == SeatStatus::Occupied).count())
-            .sum::<usize>()
-    );
+/// Parses the puzzle input.
+pub fn parse(input: &str) -> Result<Puzzle, ParseError> {
+    Ok(Puzzle {
+        seats: parse_layout(input)?,
+    })
+}
+
+/// Occupied seats once the layout stabilises, looking at adjacent seats.
+pub fn part_one(puzzle: &Puzzle) -> usize {
+    count_occupied(&run_simulation(&puzzle.seats, &count_adjacent, 4))
+}
+
+/// Occupied seats once the layout stabilises, looking at the first visible seats.
+pub fn part_two(puzzle: &Puzzle) -> usize {
+    count_occupied(&run_simulation(&puzzle.seats, &count_visible, 5))
+}
+
+/// Parses the puzzle input and solves both parts.
+pub fn solve(input: &str) -> Result<(usize, usize), ParseError> {
+    let puzzle = parse(input)?;
+    Ok((part_one(&puzzle), part_two(&puzzle)))
+}
+
+impl Solution for Puzzle {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        parse(input)
+    }
+
+    fn part_one(&self) -> Answer {
+        part_one(self).into()
+    }
+
+    fn part_two(&self) -> Answer {
+        part_two(self).into()
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Copy)]
@@ -33,39 +54,28 @@ enum SeatStatus {
     Occupied,
 }
 
-type SeatLayout = Vec<Vec<SeatStatus>>;
+type SeatLayout = Grid<SeatStatus>;
 
-fn read_seats_from_file() -> SeatLayout {
-    let file = File::open(FILEPATH).expect("File not found");
-    let reader = BufReader::new(file);
-
-    parse_layout(
-        reader
-            .lines()
-            .map(|l| l.expect("expected lines from input"))
-            .collect(),
-    )
+fn count_occupied(seats: &SeatLayout) -> usize {
+    seats
+        .cells()
+        .filter(|s| **s == SeatStatus::Occupied)
+        .count()
 }
 
-fn parse_layout(rows: Vec<String>) -> SeatLayout {
-    rows.iter()
-        .map(|row| {
-            row.chars()
-                .map(|ch| match ch {
-                    '.' => SeatStatus::Floor,
-                    '#' => SeatStatus::Occupied,
-                    'L' => SeatStatus::Empty,
-                    _ => unreachable!(),
-                })
-                .collect()
-        })
-        .collect()
+fn parse_layout(input: &str) -> Result<SeatLayout, ParseError> {
+    let seat = |ch| match ch {
+        '.' => Some(SeatStatus::Floor),
+        '#' => Some(SeatStatus::Occupied),
+        'L' => Some(SeatStatus::Empty),
+        _ => None,
+    };
+    Grid::parse(input, seat, "`.`, `#` or `L`")
 }
 
-#[allow(clippy::ptr_arg)]
 fn run_simulation(
     seats: &SeatLayout,
-    fn_count: &dyn Fn(i8, i8, &SeatLayout) -> usize,
+    fn_count: &dyn Fn(isize, isize, &SeatLayout) -> usize,
     max_occupied: usize,
 ) -> SeatLayout {
     let new_layout = &move_seats(seats, fn_count, max_occupied);
@@ -75,78 +85,56 @@ fn run_simulation(
     run_simulation(new_layout, fn_count, max_occupied)
 }
 
-#[allow(clippy::ptr_arg)]
 fn move_seats(
     seats: &SeatLayout,
-    fn_count: &dyn Fn(i8, i8, &SeatLayout) -> usize,
+    fn_count: &dyn Fn(isize, isize, &SeatLayout) -> usize,
     max_occupied: usize,
 ) -> SeatLayout {
-    let mut layout = vec![];
-    for (y, row) in seats.iter().enumerate() {
-        let mut new_row = vec![];
-        for (x, seat) in row.iter().enumerate() {
-            let count_occupied = fn_count(x as i8, y as i8, &seats);
-            if *seat == SeatStatus::Empty && count_occupied == 0 {
-                new_row.push(SeatStatus::Occupied);
-            } else if *seat == SeatStatus::Occupied && count_occupied >= max_occupied {
-                new_row.push(SeatStatus::Empty);
-            } else {
-                new_row.push(*seat);
-            }
+    Grid::from_fn(seats.width(), seats.height(), |x, y| {
+        let seat = *seats.get(x, y);
+        let count_occupied = fn_count(x as isize, y as isize, seats);
+        if seat == SeatStatus::Empty && count_occupied == 0 {
+            SeatStatus::Occupied
+        } else if seat == SeatStatus::Occupied && count_occupied >= max_occupied {
+            SeatStatus::Empty
+        } else {
+            seat
         }
-        layout.push(new_row);
-    }
-    layout
-}
-
-#[allow(clippy::ptr_arg)]
-fn count_adjacent(x: i8, y: i8, seats: &SeatLayout) -> usize {
-    let x_max = seats[0].len() as i8;
-    let y_max = seats.len() as i8;
-    vec![
-        (x - 1, y - 1),
-        (x, y - 1),
-        (x + 1, y - 1),
-        (x - 1, y),
-        (x + 1, y),
-        (x - 1, y + 1),
-        (x, y + 1),
-        (x + 1, y + 1),
-    ]
-    .iter()
-    .filter(|(x, y)| *x >= 0 && *y >= 0 && *x < x_max && *y < y_max)
-    .filter(|(x, y)| seats[*y as usize][*x as usize] == SeatStatus::Occupied)
-    .count()
-}
-
-#[allow(clippy::ptr_arg)]
-fn count_visible(x: i8, y: i8, seats: &SeatLayout) -> usize {
-    let directions: Vec<(i8, i8)> = vec![
-        (-1, -1),
-        (0, -1),
-        (1, -1),
-        (-1, 0),
-        (1, 0),
-        (-1, 1),
-        (0, 1),
-        (1, 1),
-    ];
+    })
+}
+
+const DIRECTIONS: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+fn count_adjacent(x: isize, y: isize, seats: &SeatLayout) -> usize {
+    DIRECTIONS
+        .iter()
+        .filter(|(dx, dy)| seats.get_checked(x + dx, y + dy) == Some(&SeatStatus::Occupied))
+        .count()
+}
+
+fn count_visible(x: isize, y: isize, seats: &SeatLayout) -> usize {
     let mut count = 0;
-    let height = seats.len() as i8;
-    let width = seats[0].len() as i8;
-    for direction in directions.iter() {
-        for i in 1..=max(height, width) {
-            let y_dir = y + (i as i8) * direction.1;
-            let x_dir = x + (i as i8) * direction.0;
-            if y_dir >= height || x_dir >= width || x_dir < 0 || y_dir < 0 {
-                break;
-            }
-            if seats[y_dir as usize][x_dir as usize] == SeatStatus::Occupied {
+    for (dx, dy) in DIRECTIONS.iter() {
+        let (mut x, mut y) = (x + dx, y + dy);
+        // Floor is seen through, up to the first seat or the edge of the layout
+        while let Some(seat) = seats.get_checked(x, y) {
+            if *seat == SeatStatus::Occupied {
                 count += 1;
                 break;
-            } else if seats[y_dir as usize][x_dir as usize] == SeatStatus::Empty {
+            } else if *seat == SeatStatus::Empty {
                 break;
             }
+            x += dx;
+            y += dy;
         }
     }
     count
@@ -154,55 +142,37 @@ fn count_visible(x: i8, y: i8, seats: &SeatLayout) -> usize {
 
 #[test]
 fn test_parse_layout() {
-    let rows = vec!["#.L".to_string(), "#.L".to_string()];
+    let rows = "#.L\n#.L";
 
-    let expected: SeatLayout = vec![
-        vec![SeatStatus::Occupied, SeatStatus::Floor, SeatStatus::Empty],
-        vec![SeatStatus::Occupied, SeatStatus::Floor, SeatStatus::Empty],
-    ];
+    let expected = [SeatStatus::Occupied, SeatStatus::Floor, SeatStatus::Empty];
 
-    assert_eq!(expected, parse_layout(rows));
+    let layout = parse_layout(rows).unwrap();
+    assert_eq!(2, layout.height());
+    assert_eq!(&expected[..], layout.row(0));
+    assert_eq!(&expected[..], layout.row(1));
+}
+
+#[test]
+fn test_parse_layout_invalid() {
+    let rows = "#.L\n#xL";
+    let err = parse_layout(rows).unwrap_err();
+    assert_eq!((2, 2), (err.line, err.column));
+
+    let rows = "#.L\n#.";
+    assert_eq!(2, parse_layout(rows).unwrap_err().line);
 }
 
 #[test]
 fn test_count_adjacent() {
-    let seats: SeatLayout = vec![
-        vec![
-            SeatStatus::Occupied,
-            SeatStatus::Floor,
-            SeatStatus::Occupied,
-            SeatStatus::Occupied,
-        ],
-        vec![
-            SeatStatus::Occupied,
-            SeatStatus::Occupied,
-            SeatStatus::Occupied,
-            SeatStatus::Occupied,
-        ],
-    ];
+    let seats = parse_layout("#.##\n####").unwrap();
     assert_eq!(4, count_adjacent(2, 0, &seats));
 }
 
 #[test]
 fn test_move_seats_top_row() {
-    let input: SeatLayout = vec![
-        vec![
-            SeatStatus::Occupied,
-            SeatStatus::Floor,
-            SeatStatus::Occupied,
-            SeatStatus::Occupied,
-            SeatStatus::Floor,
-        ],
-        vec![
-            SeatStatus::Occupied,
-            SeatStatus::Occupied,
-            SeatStatus::Occupied,
-            SeatStatus::Occupied,
-            SeatStatus::Occupied,
-        ],
-    ];
+    let input = parse_layout("#.##.\n#####").unwrap();
 
-    let expected_top_row = vec![
+    let expected_top_row = [
         SeatStatus::Occupied,
         SeatStatus::Floor,
         SeatStatus::Empty,
@@ -210,25 +180,20 @@ fn test_move_seats_top_row() {
         SeatStatus::Floor,
     ];
 
-    assert_eq!(expected_top_row, move_seats(&input, &count_visible, 4)[0]);
+    assert_eq!(
+        &expected_top_row[..],
+        move_seats(&input, &count_visible, 4).row(0)
+    );
 }
 
 #[test]
 fn test_count_visible_empty() {
-    let rows = vec![
-        "##...##".to_string(),
-        "...L...".to_string(),
-        "##...##".to_string(),
-    ];
-    assert_eq!(0, count_visible(3, 1, &parse_layout(rows)));
+    let rows = "##...##\n...L...\n##...##";
+    assert_eq!(0, count_visible(3, 1, &parse_layout(rows).unwrap()));
 }
 
 #[test]
 fn test_count_visible_full() {
-    let rows = vec![
-        "..#......".to_string(),
-        "..#L....#".to_string(),
-        "....#....".to_string(),
-    ];
-    assert_eq!(4, count_visible(3, 1, &parse_layout(rows)));
+    let rows = "..#......\n..#L....#\n....#....";
+    assert_eq!(4, count_visible(3, 1, &parse_layout(rows).unwrap()));
 }
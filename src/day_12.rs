@@ -1,30 +1,50 @@
-use std::fs::File;
-use std::io::{prelude::*, BufReader};
+//! Day 12: Rain Risk
 
-const FILEPATH: &str = "data/12/input.txt";
+use crate::error::{parse_field, ParseError};
+use crate::solution::{Answer, Solution};
 
-pub fn first_solution() {
-    let instructions = read_instructions_from_file();
-    println!("Solution: {}", count_distance(navigate(&instructions)));
+/// The navigation instructions.
+#[derive(Debug)]
+pub struct Puzzle {
+    instructions: Vec<Instruction>,
 }
 
-pub fn second_solution() {
-    let instructions = read_instructions_from_file();
-    println!(
-        "Solution: {}",
-        count_distance(navigate_waypoint(&instructions, &mut (10, 1)))
-    );
+/// Parses the puzzle input.
+pub fn parse(input: &str) -> Result<Puzzle, ParseError> {
+    let instructions: Vec<String> = input.lines().map(|l| l.to_string()).collect();
+    Ok(Puzzle {
+        instructions: parse_instructions(&instructions)?,
+    })
+}
+
+/// Manhattan distance travelled when the instructions move the ship.
+pub fn part_one(puzzle: &Puzzle) -> i32 {
+    count_distance(navigate(&puzzle.instructions))
+}
+
+/// Manhattan distance travelled when the instructions move the waypoint.
+pub fn part_two(puzzle: &Puzzle) -> i32 {
+    count_distance(navigate_waypoint(&puzzle.instructions, &mut (10, 1)))
+}
+
+/// Parses the puzzle input and solves both parts.
+pub fn solve(input: &str) -> Result<(i32, i32), ParseError> {
+    let puzzle = parse(input)?;
+    Ok((part_one(&puzzle), part_two(&puzzle)))
 }
 
-fn read_instructions_from_file() -> Vec<Instruction> {
-    let file = File::open(FILEPATH).expect("File not found");
-    let reader = BufReader::new(file);
+impl Solution for Puzzle {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        parse(input)
+    }
+
+    fn part_one(&self) -> Answer {
+        part_one(self).into()
+    }
 
-    let instructions: Vec<String> = reader
-        .lines()
-        .map(|l| l.expect("expected lines from input"))
-        .collect();
-    parse_instructions(&instructions)
+    fn part_two(&self) -> Answer {
+        part_two(self).into()
+    }
 }
 
 #[derive(Debug)]
@@ -44,26 +64,46 @@ struct Instruction {
     value: i32,
 }
 
-fn parse_instructions(instructions: &[String]) -> Vec<Instruction> {
+fn parse_instructions(instructions: &[String]) -> Result<Vec<Instruction>, ParseError> {
     instructions
         .iter()
-        .map(|s| s.split_at(1))
-        .map(|(d, v)| Instruction {
-            direction: match d {
-                "N" => Direction::North,
-                "S" => Direction::South,
-                "E" => Direction::East,
-                "W" => Direction::West,
-                "R" => Direction::Right,
-                "L" => Direction::Left,
-                "F" => Direction::Forward,
-                _ => unreachable!(),
-            },
-            value: v.parse::<i32>().expect("numeric value expected"),
-        })
+        .enumerate()
+        .map(|(i, s)| parse_instruction(s).map_err(|e| e.offset(i)))
         .collect()
 }
 
+fn parse_instruction(s: &str) -> Result<Instruction, ParseError> {
+    let d = s.chars().next().map_or("", |c| &s[..c.len_utf8()]);
+    let v = &s[d.len()..];
+    let direction = match d {
+        "N" => Direction::North,
+        "S" => Direction::South,
+        "E" => Direction::East,
+        "W" => Direction::West,
+        "R" => Direction::Right,
+        "L" => Direction::Left,
+        "F" => Direction::Forward,
+        _ => {
+            return Err(ParseError::new(
+                s,
+                d,
+                format!("expected one of N, S, E, W, R, L or F, found `{}`", d),
+            ))
+        }
+    };
+    let value = parse_field::<i32>(s, v, "a number")?;
+    if let Direction::Right | Direction::Left = direction {
+        if value < 0 || value % 90 != 0 {
+            return Err(ParseError::new(
+                s,
+                v,
+                format!("expected a multiple of 90 degrees, found {}", value),
+            ));
+        }
+    }
+    Ok(Instruction { direction, value })
+}
+
 fn navigate(instructions: &[Instruction]) -> (i32, i32) {
     let mut pos = (0, 0);
     let mut dir = 0;
@@ -73,15 +113,8 @@ fn navigate(instructions: &[Instruction]) -> (i32, i32) {
             Direction::South => pos.1 -= instruction.value,
             Direction::East => pos.0 += instruction.value,
             Direction::West => pos.0 -= instruction.value,
-            Direction::Right => {
-                dir -= instruction.value;
-                dir += 3600;
-                dir %= 360
-            }
-            Direction::Left => {
-                dir += instruction.value;
-                dir %= 360
-            }
+            Direction::Right => dir = (dir - instruction.value % 360).rem_euclid(360),
+            Direction::Left => dir = (dir + instruction.value % 360) % 360,
             Direction::Forward => match dir {
                 0 => pos.0 += instruction.value,
                 90 => pos.1 += instruction.value,
@@ -153,6 +186,16 @@ fn count_distance(position: (i32, i32)) -> i32 {
     position.0.abs() + position.1.abs()
 }
 
+#[test]
+fn test_parse_instructions_invalid() {
+    let instructions = vec!["F10".to_string(), "X3".to_string()];
+    let err = parse_instructions(&instructions).unwrap_err();
+    assert_eq!((2, 1), (err.line, err.column));
+
+    let instructions = vec!["R45".to_string()];
+    assert_eq!(2, parse_instructions(&instructions).unwrap_err().column);
+}
+
 #[test]
 fn test_count_distance() {
     let instructions = vec![
@@ -164,7 +207,7 @@ fn test_count_distance() {
     ];
     assert_eq!(
         25,
-        count_distance(navigate(&parse_instructions(&instructions)))
+        count_distance(navigate(&parse_instructions(&instructions).unwrap()))
     );
 }
 
@@ -180,8 +223,15 @@ fn test_count_distance_waypoint() {
     assert_eq!(
         286,
         count_distance(navigate_waypoint(
-            &parse_instructions(&instructions),
+            &parse_instructions(&instructions).unwrap(),
             &mut (10, 1)
         ))
     );
 }
+
+#[test]
+fn test_navigate_large_turns() {
+    let instructions = vec!["R3690".to_string(), "F1".to_string(), "L450".to_string()];
+    let instructions = parse_instructions(&instructions).unwrap();
+    assert_eq!((0, -1), navigate(&instructions));
+}
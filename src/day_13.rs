@@ -1,30 +1,78 @@
-use std::fs::File;
-use std::io::{prelude::*, BufReader};
+//! Day 13: Shuttle Search
 
-const FILEPATH: &str = "data/13/input.txt";
+use crate::error::{parse_field, ParseError};
+pub use crate::modular::{chinese_remainder, egcd, mod_inv};
+use crate::solution::{Answer, Solution};
 
-pub fn first_solution() {
-    let (timestamp, buses) = parse_timestamp_and_buses();
-    let bus = earliest_bus(timestamp, buses);
-    println!("Solution: {}", bus.0 * bus.1);
+/// The earliest departure timestamp and the bus schedule.
+#[derive(Debug)]
+pub struct Puzzle {
+    timestamp: u32,
+    buses: String,
 }
 
-pub fn second_solution() {
-    let (_, buses) = parse_timestamp_and_buses();
-    println!("Solution: {}", earliest_timestamp_fast(&buses));
+/// Parses the puzzle input.
+pub fn parse(input: &str) -> Result<Puzzle, ParseError> {
+    let (timestamp, buses) = parse_timestamp_and_buses(input)?;
+    Ok(Puzzle { timestamp, buses })
 }
 
-fn parse_timestamp_and_buses() -> (u32, String) {
-    let file = File::open(FILEPATH).expect("File not found");
-    let reader = BufReader::new(file);
-    let lines: Vec<String> = reader
-        .lines()
-        .map(|l| l.expect("expected lines from input"))
-        .collect();
-    (
-        lines[0].parse::<u32>().expect("numeric timestamp expected"),
-        lines[1].clone(),
-    )
+/// Id of the earliest bus multiplied by the minutes to wait for it.
+pub fn part_one(puzzle: &Puzzle) -> u32 {
+    let bus = earliest_bus(puzzle.timestamp, puzzle.buses.clone());
+    bus.0 * bus.1
+}
+
+/// Earliest timestamp at which every bus departs at its offset in the schedule.
+pub fn part_two(puzzle: &Puzzle) -> Option<i64> {
+    earliest_timestamp_fast(&puzzle.buses)
+}
+
+/// Parses the puzzle input and solves both parts.
+pub fn solve(input: &str) -> Result<(u32, Option<i64>), ParseError> {
+    let puzzle = parse(input)?;
+    Ok((part_one(&puzzle), part_two(&puzzle)))
+}
+
+impl Solution for Puzzle {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        parse(input)
+    }
+
+    fn part_one(&self) -> Answer {
+        part_one(self).into()
+    }
+
+    fn part_two(&self) -> Answer {
+        part_two(self).into()
+    }
+}
+
+/*
+ * 939
+ * 7,13,x,x,59,x,31,19
+ */
+fn parse_timestamp_and_buses(input: &str) -> Result<(u32, String), ParseError> {
+    let mut lines = input.lines();
+    let timestamp = lines.next().unwrap_or("");
+    let timestamp = parse_field::<u32>(timestamp, timestamp, "a timestamp")?;
+    let buses = match lines.next() {
+        Some(buses) => buses,
+        None => return Err(ParseError::new("", "", "expected a list of buses").offset(1)),
+    };
+    for bus in buses.split(',').filter(|b| *b != "x") {
+        match parse_field::<u32>(buses, bus, "a bus id or `x`") {
+            Ok(0) => {
+                return Err(ParseError::new(buses, bus, "expected a non-zero bus id").offset(1))
+            }
+            Err(err) => return Err(err.offset(1)),
+            Ok(_) => {}
+        }
+    }
+    if buses.split(',').all(|b| b == "x") {
+        return Err(ParseError::new(buses, buses, "expected at least one bus id").offset(1));
+    }
+    Ok((timestamp, buses.to_string()))
 }
 
 fn earliest_bus(timestamp: u32, buses: String) -> (u32, u32) {
@@ -34,7 +82,7 @@ fn earliest_bus(timestamp: u32, buses: String) -> (u32, u32) {
         .map(|n| n.parse::<u32>().expect("numeric expected"))
         .map(|n| (n, timestamp - timestamp % n + n))
         .collect();
-    next_buses.sort_by(|a, b| a.1.cmp(&b.1));
+    next_buses.sort_by_key(|b| b.1);
     (next_buses[0].0, next_buses[0].1 - timestamp)
 }
 
@@ -46,7 +94,7 @@ fn earliest_timestamp_slow(input: &str) -> u64 {
         .filter(|(_, c)| *c != "x")
         .map(|(n, c)| (n, c.parse::<u64>().expect("numeric expected")))
         .collect();
-    let num_buses = buses.iter().count();
+    let num_buses = buses.len();
     let mut start = 0;
     loop {
         start += 1;
@@ -65,7 +113,8 @@ fn earliest_timestamp_slow(input: &str) -> u64 {
     start
 }
 
-fn earliest_timestamp_fast(input: &str) -> i64 {
+// None when bus ids sharing a factor make the offsets impossible to meet.
+fn earliest_timestamp_fast(input: &str) -> Option<i64> {
     let buses: Vec<(i64, i64)> = input
         .split(',')
         .enumerate()
@@ -77,40 +126,18 @@ fn earliest_timestamp_fast(input: &str) -> i64 {
         &buses.iter().map(|&(n, b)| b - n).collect::<Vec<_>>(),
         &buses.iter().map(|&(_, b)| b).collect::<Vec<_>>(),
     )
-    .expect("No solution found")
-}
-
-// From https://rosettacode.org/wiki/Chinese_remainder_theorem#Rust
-#[allow(clippy::many_single_char_names)]
-fn egcd(a: i64, b: i64) -> (i64, i64, i64) {
-    if a == 0 {
-        (b, 0, 1)
-    } else {
-        let (g, x, y) = egcd(b % a, a);
-        (g, y - (b / a) * x, x)
-    }
 }
 
-// From https://rosettacode.org/wiki/Chinese_remainder_theorem#Rust
-fn mod_inv(x: i64, n: i64) -> Option<i64> {
-    let (g, x, _) = egcd(x, n);
-    if g == 1 {
-        Some((x % n + n) % n)
-    } else {
-        None
-    }
-}
-
-// From https://rosettacode.org/wiki/Chinese_remainder_theorem#Rust
-fn chinese_remainder(residues: &[i64], modulii: &[i64]) -> Option<i64> {
-    let prod = modulii.iter().product::<i64>();
-    let mut sum = 0;
-    for (&residue, &modulus) in residues.iter().zip(modulii) {
-        let p = prod / modulus;
-        sum += residue * mod_inv(p, modulus)? * p
-    }
-
-    Some(sum % prod)
+#[test]
+fn test_parse_timestamp_and_buses_invalid() {
+    let err = parse_timestamp_and_buses("939\n7,13,y,59").unwrap_err();
+    assert_eq!((2, 6), (err.line, err.column));
+    assert_eq!(2, parse_timestamp_and_buses("939").unwrap_err().line);
+    let err = parse_timestamp_and_buses("939\nx,x").unwrap_err();
+    assert_eq!(
+        (2, "expected at least one bus id"),
+        (err.line, err.message.as_str())
+    );
 }
 
 #[test]
@@ -123,5 +150,6 @@ fn test_get_earliest_bus() {
 fn test_find_timestamp() {
     let input = "17,x,13,19".to_string();
     assert_eq!(3417, earliest_timestamp_slow(&input));
-    assert_eq!(3417, earliest_timestamp_fast(&input));
+    assert_eq!(Some(3417), earliest_timestamp_fast(&input));
+    assert_eq!(None, earliest_timestamp_fast("4,6"));
 }
@@ -1,67 +1,151 @@
+//! Day 14: Docking Data
+
 use std::collections::HashMap;
-use std::fs::File;
-use std::io::{prelude::*, BufReader};
 
 use lazy_static::lazy_static;
 use regex::Regex;
 
-const FILEPATH: &str = "data/14/input.txt";
+use crate::error::{parse_field, parse_lines, ParseError};
+use crate::solution::{Answer, Solution};
+
+/// The initialization program.
+#[derive(Debug)]
+pub struct Puzzle {
+    program: Vec<Operation>,
+}
+
+/// Parses the puzzle input.
+pub fn parse(input: &str) -> Result<Puzzle, ParseError> {
+    Ok(Puzzle {
+        program: parse_program(input)?,
+    })
+}
 
-pub fn first_solution() {
-    println!("{}", run_program_v1(parse_lines()));
+/// Sum of the memory values after running the program with the version 1 decoder.
+pub fn part_one(puzzle: &Puzzle) -> u64 {
+    run_program_v1(&puzzle.program)
 }
 
-pub fn second_solution() {
-    println!("{}", run_program_v2(parse_lines()));
+/// Sum of the memory values after running the program with the version 2 decoder.
+pub fn part_two(puzzle: &Puzzle) -> u64 {
+    run_program_v2(&puzzle.program)
 }
 
-fn parse_lines() -> Vec<String> {
-    let file = File::open(FILEPATH).expect("File not found");
-    let reader = BufReader::new(file);
-    reader
-        .lines()
-        .map(|l| l.expect("error reading line"))
-        .collect()
+/// Parses the puzzle input and solves both parts.
+pub fn solve(input: &str) -> Result<(u64, u64), ParseError> {
+    let puzzle = parse(input)?;
+    Ok((part_one(&puzzle), part_two(&puzzle)))
+}
+
+impl Solution for Puzzle {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        parse(input)
+    }
+
+    fn part_one(&self) -> Answer {
+        part_one(self).into()
+    }
+
+    fn part_two(&self) -> Answer {
+        part_two(self).into()
+    }
 }
 
 type Mask = Vec<char>;
 
+#[derive(Debug)]
 struct Memory {
     address: u64,
     value: u64,
 }
 
-fn parse_mask(input: String) -> Mask {
-    input.chars().skip(7).collect()
+#[derive(Debug)]
+enum Operation {
+    Mask(Mask),
+    Write(Memory),
+}
+
+const MAX_VALUE: u64 = (1 << 36) - 1;
+
+fn parse_program(input: &str) -> Result<Vec<Operation>, ParseError> {
+    let program = parse_lines(input, |line| {
+        if line.starts_with("mask") {
+            Ok(Operation::Mask(parse_mask(line)?))
+        } else {
+            Ok(Operation::Write(parse_memory(line)?))
+        }
+    })?;
+    if let Some(Operation::Write(_)) = program.first() {
+        let line = input.lines().next().unwrap_or("");
+        return Err(ParseError::new(
+            line,
+            line,
+            "expected a mask before the first memory write",
+        ));
+    }
+    Ok(program)
+}
+
+fn parse_mask(input: &str) -> Result<Mask, ParseError> {
+    let mask = input
+        .strip_prefix("mask = ")
+        .ok_or_else(|| ParseError::new(input, input, "expected `mask = <36 bits>`"))?;
+    if let Some((i, ch)) = mask.char_indices().find(|(_, c)| !"X01".contains(*c)) {
+        return Err(ParseError::new(
+            input,
+            &mask[i..],
+            format!("expected `X`, `0` or `1`, found `{}`", ch),
+        ));
+    }
+    if mask.len() != 36 {
+        return Err(ParseError::new(input, mask, "expected a mask of 36 bits"));
+    }
+    Ok(mask.chars().collect())
 }
 
 lazy_static! {
     static ref RE_MEM: Regex = Regex::new(r"^mem\[(\d+)\]\s=\s(\d+)$").expect("Invalid regex");
 }
 
-fn parse_memory(input: String) -> Memory {
-    let groups = RE_MEM.captures(&input).expect("Invalid input");
-    Memory {
-        address: groups[1].parse().expect("invalid numeric value"),
-        value: groups[2].parse().expect("invalid numeric value"),
+fn parse_memory(input: &str) -> Result<Memory, ParseError> {
+    let groups = RE_MEM
+        .captures(input)
+        .ok_or_else(|| ParseError::new(input, input, "expected `mem[<address>] = <value>`"))?;
+    let mut fields = [0; 2];
+    for (i, field) in fields.iter_mut().enumerate() {
+        let text = groups.get(i + 1).expect("expected capture group").as_str();
+        *field = parse_field(input, text, "a 36-bit number")?;
+        if *field > MAX_VALUE {
+            return Err(ParseError::new(
+                input,
+                text,
+                format!("expected a 36-bit number, found {}", text),
+            ));
+        }
     }
+    Ok(Memory {
+        address: fields[0],
+        value: fields[1],
+    })
 }
 
-fn run_program_v1(lines: Vec<String>) -> u64 {
-    let mut mask: Mask = vec![];
+fn run_program_v1(program: &[Operation]) -> u64 {
+    let mut mask: &Mask = &vec![];
     let mut memory: HashMap<u64, u64> = HashMap::new();
-    for line in lines {
-        if line.starts_with("mask") {
-            mask = parse_mask(line);
-            continue;
-        }
-        let mem = parse_memory(line);
+    for operation in program {
+        let mem = match operation {
+            Operation::Mask(m) => {
+                mask = m;
+                continue;
+            }
+            Operation::Write(mem) => mem,
+        };
 
-        let value = compute_decoder_v1(&mask, mem.value);
+        let value = compute_decoder_v1(mask, mem.value);
 
         memory.insert(mem.address, value);
     }
-    memory.iter().map(|(_, v)| v).sum()
+    memory.values().sum()
 }
 
 fn compute_decoder_v1(mask: &Mask, value: u64) -> u64 {
@@ -81,21 +165,23 @@ fn compute_decoder_v1(mask: &Mask, value: u64) -> u64 {
     result.iter().fold(0, |res, bit| (res << 1) ^ bit)
 }
 
-fn run_program_v2(lines: Vec<String>) -> u64 {
-    let mut mask: Mask = vec![];
+fn run_program_v2(program: &[Operation]) -> u64 {
+    let mut mask: &Mask = &vec![];
     let mut memory: HashMap<u64, u64> = HashMap::new();
-    for line in lines {
-        if line.starts_with("mask") {
-            mask = parse_mask(line);
-            continue;
-        }
-        let mem = parse_memory(line);
+    for operation in program {
+        let mem = match operation {
+            Operation::Mask(m) => {
+                mask = m;
+                continue;
+            }
+            Operation::Write(mem) => mem,
+        };
 
-        for addr in compute_addresses(&mask, mem.address) {
+        for addr in compute_addresses(mask, mem.address) {
             memory.insert(addr, mem.value);
         }
     }
-    memory.iter().map(|(_, v)| v).sum()
+    memory.values().sum()
 }
 
 fn compute_addresses(mask: &Mask, value: u64) -> Vec<u64> {
@@ -135,7 +221,12 @@ fn compute_addresses(mask: &Mask, value: u64) -> Vec<u64> {
     results
 }
 
+// Every string of `num` bits, grown from the one-bit strings `nums`. A mask without
+// floating bits has a single address, and so a single empty string of bits.
 fn perms(num: usize, nums: Vec<String>) -> Vec<String> {
+    if num == 0 {
+        return vec![String::new()];
+    }
     if num == 1 {
         return nums;
     }
@@ -149,43 +240,65 @@ fn perms(num: usize, nums: Vec<String>) -> Vec<String> {
 
 #[test]
 fn test_parse_mask() {
-    let input = "mask = X101011X011X10101011000001X00XX0X000".to_string();
-    assert_eq!('X', parse_mask(input)[0]);
+    let input = "mask = X101011X011X10101011000001X00XX0X000";
+    assert_eq!('X', parse_mask(input).unwrap()[0]);
 }
 
 #[test]
 fn test_parse_memory() {
-    let input = "mem[54849] = 40771927".to_string();
-    let expected = parse_memory(input);
+    let input = "mem[54849] = 40771927";
+    let expected = parse_memory(input).unwrap();
     assert_eq!(54849, expected.address);
     assert_eq!(40771927, expected.value);
 }
 
 #[test]
 fn test_compute_value() {
-    let mask = parse_mask("mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X".to_string());
+    let mask = parse_mask("mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X").unwrap();
     assert_eq!(101, compute_decoder_v1(&mask, 101));
     assert_eq!(73, compute_decoder_v1(&mask, 11));
 }
 
 #[test]
 fn test_run_program_v1() {
-    let input = vec![
-        "mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X".to_string(),
-        "mem[8] = 11".to_string(),
-        "mem[7] = 101".to_string(),
-        "mem[8] = 0".to_string(),
+    let input = [
+        "mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X",
+        "mem[8] = 11",
+        "mem[7] = 101",
+        "mem[8] = 0",
     ];
-    assert_eq!(165, run_program_v1(input));
+    assert_eq!(
+        165,
+        run_program_v1(&parse_program(&input.join("\n")).unwrap())
+    );
 }
 
 #[test]
 fn test_run_program_v2() {
-    let input = vec![
-        "mask = 000000000000000000000000000000X1001X".to_string(),
-        "mem[42] = 100".to_string(),
-        "mask = 00000000000000000000000000000000X0XX".to_string(),
-        "mem[26] = 1".to_string(),
+    let input = [
+        "mask = 000000000000000000000000000000X1001X",
+        "mem[42] = 100",
+        "mask = 00000000000000000000000000000000X0XX",
+        "mem[26] = 1",
     ];
-    assert_eq!(208, run_program_v2(input));
+    assert_eq!(
+        208,
+        run_program_v2(&parse_program(&input.join("\n")).unwrap())
+    );
+}
+
+#[test]
+fn test_parse_program_invalid() {
+    let err = parse_program("mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X\nmem[8] = x").unwrap_err();
+    assert_eq!((2, 1), (err.line, err.column));
+    let err = parse_mask("mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX2X").unwrap_err();
+    assert_eq!(42, err.column);
+    assert!(parse_program("mem[8] = 11").is_err());
+    assert!(parse_memory("mem[8] = 68719476736").is_err());
+}
+
+#[test]
+fn test_compute_addresses_without_floating_bits() {
+    let mask = parse_mask("mask = 000000000000000000000000000000000001").unwrap();
+    assert_eq!(vec![43], compute_addresses(&mask, 42));
 }
@@ -0,0 +1,128 @@
+//! Day 15: Rambunctious Recitation
+
+use crate::error::{parse_field, ParseError};
+use crate::solution::{Answer, Solution};
+
+/// The starting numbers of the memory game.
+#[derive(Debug)]
+pub struct Puzzle {
+    numbers: Vec<u32>,
+}
+
+/// Parses the puzzle input.
+pub fn parse(input: &str) -> Result<Puzzle, ParseError> {
+    Ok(Puzzle {
+        numbers: parse_numbers(input)?,
+    })
+}
+
+/// The 2020th number spoken.
+pub fn part_one(puzzle: &Puzzle) -> u32 {
+    play(&puzzle.numbers, 2020)
+}
+
+/// The 30,000,000th number spoken.
+pub fn part_two(puzzle: &Puzzle) -> u32 {
+    play(&puzzle.numbers, 30_000_000)
+}
+
+/// Parses the puzzle input and solves both parts.
+pub fn solve(input: &str) -> Result<(u32, u32), ParseError> {
+    let puzzle = parse(input)?;
+    Ok((part_one(&puzzle), part_two(&puzzle)))
+}
+
+impl Solution for Puzzle {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        parse(input)
+    }
+
+    fn part_one(&self) -> Answer {
+        part_one(self).into()
+    }
+
+    fn part_two(&self) -> Answer {
+        part_two(self).into()
+    }
+}
+
+/*
+ * 0,3,6
+ */
+fn parse_numbers(input: &str) -> Result<Vec<u32>, ParseError> {
+    let line = input.lines().next().unwrap_or("");
+    if line.is_empty() {
+        return Err(ParseError::new(line, line, "expected starting numbers"));
+    }
+    line.split(',')
+        .map(|n| parse_field(line, n, "a number"))
+        .collect()
+}
+
+/// Number spoken on turn `turns`, counted from 1, of the memory game started
+/// with `numbers`.
+///
+/// Every number spoken after the starting ones is a number of turns, so it is
+/// below `turns`: the turn each such number was last spoken on is kept in a
+/// flat array of `turns` entries indexed by the number, 0 meaning never spoken.
+/// Larger starting numbers are never spoken again and are not recorded.
+pub fn play(numbers: &[u32], turns: u32) -> u32 {
+    if turns as usize <= numbers.len() {
+        return numbers[turns as usize - 1];
+    }
+    let (&last, start) = numbers.split_last().expect("expected starting numbers");
+    let mut last_seen = vec![0u32; turns as usize];
+    for (turn, &number) in start.iter().enumerate() {
+        if let Some(seen) = last_seen.get_mut(number as usize) {
+            *seen = turn as u32 + 1;
+        }
+    }
+
+    let mut current = last;
+    for turn in numbers.len() as u32..turns {
+        let seen = match last_seen.get_mut(current as usize) {
+            Some(seen) => std::mem::replace(seen, turn),
+            // Only the last starting number can be out of the array
+            None => start
+                .iter()
+                .rposition(|&n| n == current)
+                .map_or(0, |i| i as u32 + 1),
+        };
+        current = if seen == 0 { 0 } else { turn - seen };
+    }
+    current
+}
+
+#[test]
+fn test_play() {
+    assert_eq!(0, play(&[0, 3, 6], 4));
+    assert_eq!(3, play(&[0, 3, 6], 5));
+    assert_eq!(3, play(&[0, 3, 6], 6));
+    assert_eq!(0, play(&[0, 3, 6], 10));
+    assert_eq!(436, play(&[0, 3, 6], 2020));
+    assert_eq!(1, play(&[1, 3, 2], 2020));
+    assert_eq!(10, play(&[2, 1, 3], 2020));
+    assert_eq!(27, play(&[1, 2, 3], 2020));
+    assert_eq!(78, play(&[2, 3, 1], 2020));
+    assert_eq!(438, play(&[3, 2, 1], 2020));
+    assert_eq!(1836, play(&[3, 1, 2], 2020));
+}
+
+#[test]
+fn test_play_large_starting_numbers() {
+    assert_eq!(0, play(&[0, 3, 4_000_000_000], 4));
+    assert_eq!(2, play(&[4_000_000_000, 1, 4_000_000_000], 4));
+    assert_eq!(1, play(&[4_000_000_000, 1, 4_000_000_000], 7));
+}
+
+#[test]
+fn test_play_long() {
+    assert_eq!(175594, play(&[0, 3, 6], 30_000_000));
+}
+
+#[test]
+fn test_parse_numbers() {
+    assert_eq!(vec![0, 3, 6], parse_numbers("0,3,6\n").unwrap());
+    let err = parse_numbers("0,x,6").unwrap_err();
+    assert_eq!(3, err.column);
+}
@@ -0,0 +1,248 @@
+//! Day 16: Ticket Translation
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::error::{parse_field, ParseError};
+use crate::solution::{Answer, Solution};
+
+lazy_static! {
+    static ref RE: Regex =
+        Regex::new(r"^([a-z ]+): (\d+)-(\d+) or (\d+)-(\d+)$").expect("Invalid regex");
+}
+
+/// The ticket field rules, your ticket and the nearby tickets.
+#[derive(Debug)]
+pub struct Puzzle {
+    rules: Vec<Rule>,
+    ticket: Vec<u32>,
+    nearby: Vec<Vec<u32>>,
+}
+
+/// Parses the puzzle input.
+pub fn parse(input: &str) -> Result<Puzzle, ParseError> {
+    parse_notes(input)
+}
+
+/// Sum of the nearby ticket values that are not valid for any field.
+pub fn part_one(puzzle: &Puzzle) -> u32 {
+    puzzle
+        .nearby
+        .iter()
+        .flatten()
+        .filter(|&&value| !is_valid_for_any(&puzzle.rules, value))
+        .sum()
+}
+
+/// Product of the six fields of your ticket whose name starts with `departure`.
+pub fn part_two(puzzle: &Puzzle) -> Option<u64> {
+    let fields = assign_fields(&puzzle.rules, &valid_tickets(puzzle))?;
+    let product = fields
+        .iter()
+        .zip(puzzle.ticket.iter())
+        .filter(|(&rule, _)| puzzle.rules[rule].name.starts_with("departure"))
+        .map(|(_, &value)| value as u64)
+        .product();
+    Some(product)
+}
+
+/// Parses the puzzle input and solves both parts.
+pub fn solve(input: &str) -> Result<(u32, Option<u64>), ParseError> {
+    let puzzle = parse(input)?;
+    Ok((part_one(&puzzle), part_two(&puzzle)))
+}
+
+impl Solution for Puzzle {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        parse(input)
+    }
+
+    fn part_one(&self) -> Answer {
+        part_one(self).into()
+    }
+
+    fn part_two(&self) -> Answer {
+        part_two(self).into()
+    }
+}
+
+#[derive(Debug)]
+struct Rule {
+    name: String,
+    ranges: [(u32, u32); 2],
+}
+
+impl Rule {
+    fn contains(&self, value: u32) -> bool {
+        self.ranges
+            .iter()
+            .any(|&(low, high)| low <= value && value <= high)
+    }
+}
+
+fn is_valid_for_any(rules: &[Rule], value: u32) -> bool {
+    rules.iter().any(|rule| rule.contains(value))
+}
+
+/*
+ * class: 1-3 or 5-7
+ * row: 6-11 or 33-44
+ *
+ * your ticket:
+ * 7,1
+ *
+ * nearby tickets:
+ * 7,3
+ * 40,4
+ */
+fn parse_notes(input: &str) -> Result<Puzzle, ParseError> {
+    let lines: Vec<&str> = input.lines().collect();
+    let mut sections = lines.split(|line| line.is_empty());
+    let mut offset = 0;
+
+    let section = sections.next().unwrap_or(&[]);
+    let mut rules = vec![];
+    for (i, line) in section.iter().enumerate() {
+        rules.push(parse_rule(line).map_err(|e| e.offset(offset + i))?);
+    }
+    offset += section.len() + 1;
+
+    let section = sections.next().unwrap_or(&[]);
+    let mut tickets = parse_tickets(section, "your ticket:", rules.len(), offset)?;
+    if tickets.len() != 1 {
+        let index = tickets.len().min(2);
+        let line = section.get(index).unwrap_or(&"");
+        return Err(ParseError::new(line, line, "expected a single ticket").offset(offset + index));
+    }
+    let ticket = tickets.remove(0);
+    offset += section.len() + 1;
+
+    let section = sections.next().unwrap_or(&[]);
+    let nearby = parse_tickets(section, "nearby tickets:", rules.len(), offset)?;
+
+    Ok(Puzzle {
+        rules,
+        ticket,
+        nearby,
+    })
+}
+
+fn parse_rule(line: &str) -> Result<Rule, ParseError> {
+    let groups = RE.captures(line).ok_or_else(|| {
+        ParseError::new(line, line, "expected `<field>: <min>-<max> or <min>-<max>`")
+    })?;
+    Ok(Rule {
+        name: groups[1].to_string(),
+        ranges: [
+            (
+                parse_field(line, &groups[2], "a number")?,
+                parse_field(line, &groups[3], "a number")?,
+            ),
+            (
+                parse_field(line, &groups[4], "a number")?,
+                parse_field(line, &groups[5], "a number")?,
+            ),
+        ],
+    })
+}
+
+// Parses a `header` line followed by tickets of `fields` values each.
+fn parse_tickets(
+    section: &[&str],
+    header: &str,
+    fields: usize,
+    offset: usize,
+) -> Result<Vec<Vec<u32>>, ParseError> {
+    let first = section.first().unwrap_or(&"");
+    if *first != header {
+        return Err(ParseError::new(first, first, format!("expected `{}`", header)).offset(offset));
+    }
+    let mut tickets = vec![];
+    for (i, line) in section.iter().enumerate().skip(1) {
+        let ticket = parse_ticket(line, fields).map_err(|e| e.offset(offset + i))?;
+        tickets.push(ticket);
+    }
+    Ok(tickets)
+}
+
+fn parse_ticket(line: &str, fields: usize) -> Result<Vec<u32>, ParseError> {
+    let ticket = line
+        .split(',')
+        .map(|value| parse_field(line, value, "a number"))
+        .collect::<Result<Vec<u32>, ParseError>>()?;
+    if ticket.len() != fields {
+        return Err(ParseError::new(
+            line,
+            line,
+            format!("expected {} values, found {}", fields, ticket.len()),
+        ));
+    }
+    Ok(ticket)
+}
+
+// Nearby tickets whose values are all valid for some field, and your ticket.
+fn valid_tickets(puzzle: &Puzzle) -> Vec<&[u32]> {
+    puzzle
+        .nearby
+        .iter()
+        .filter(|ticket| ticket.iter().all(|&v| is_valid_for_any(&puzzle.rules, v)))
+        .chain(std::iter::once(&puzzle.ticket))
+        .map(|ticket| ticket.as_slice())
+        .collect()
+}
+
+// Finds the rule of every column by elimination: a column that only one rule
+// fits gets that rule, which is then ruled out for every other column. Returns
+// the rule index of each column, or `None` if the elimination gets stuck.
+fn assign_fields(rules: &[Rule], tickets: &[&[u32]]) -> Option<Vec<usize>> {
+    let mut candidates: Vec<Vec<usize>> = (0..rules.len())
+        .map(|column| {
+            (0..rules.len())
+                .filter(|&rule| tickets.iter().all(|t| rules[rule].contains(t[column])))
+                .collect()
+        })
+        .collect();
+
+    let mut fields = vec![None; rules.len()];
+    for _ in 0..rules.len() {
+        let column = candidates.iter().position(|c| c.len() == 1)?;
+        let rule = candidates[column][0];
+        fields[column] = Some(rule);
+        for candidate in candidates.iter_mut() {
+            candidate.retain(|&r| r != rule);
+        }
+    }
+    fields.into_iter().collect()
+}
+
+#[test]
+fn test_error_rate() {
+    let input = "class: 1-3 or 5-7\nrow: 6-11 or 33-44\nseat: 13-40 or 45-50\n\nyour ticket:\n7,1,14\n\nnearby tickets:\n7,3,47\n40,4,50\n55,2,20\n38,6,12";
+    let puzzle = parse(input).unwrap();
+    assert_eq!(71, part_one(&puzzle));
+    assert_eq!(2, valid_tickets(&puzzle).len());
+}
+
+#[test]
+fn test_assign_fields() {
+    let input = "class: 0-1 or 4-19\nrow: 0-5 or 8-19\nseat: 0-13 or 16-19\n\nyour ticket:\n11,12,13\n\nnearby tickets:\n3,9,18\n15,1,5\n5,14,9";
+    let puzzle = parse(input).unwrap();
+    let fields = assign_fields(&puzzle.rules, &valid_tickets(&puzzle)).unwrap();
+    let names: Vec<&str> = fields
+        .iter()
+        .map(|&rule| puzzle.rules[rule].name.as_str())
+        .collect();
+    assert_eq!(vec!["row", "class", "seat"], names);
+}
+
+#[test]
+fn test_parse_notes_errors() {
+    let err = parse("class: 1-3 or x\n\nyour ticket:\n7").unwrap_err();
+    assert_eq!((1, 1), (err.line, err.column));
+    let err = parse("class: 1-3 or 5-7\n\nyour ticket:\n7\n\nnearby:\n7").unwrap_err();
+    assert_eq!("expected `nearby tickets:`", err.message);
+    assert_eq!(6, err.line);
+    let err = parse("class: 1-3 or 5-7\n\nyour ticket:\n7\n\nnearby tickets:\n7,3").unwrap_err();
+    assert_eq!("expected 1 values, found 2", err.message);
+    assert_eq!(7, err.line);
+}
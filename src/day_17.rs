@@ -0,0 +1,152 @@
+//! Day 17: Conway Cubes
+
+use std::collections::{HashMap, HashSet};
+
+use itertools::Itertools;
+
+use crate::error::ParseError;
+use crate::solution::{Answer, Solution};
+
+/// The active cubes of the initial 2-D slice.
+#[derive(Debug)]
+pub struct Puzzle {
+    slice: Vec<(i32, i32)>,
+}
+
+/// Parses the puzzle input.
+pub fn parse(input: &str) -> Result<Puzzle, ParseError> {
+    Ok(Puzzle {
+        slice: parse_slice(input)?,
+    })
+}
+
+/// Active cubes after six cycles in three dimensions.
+pub fn part_one(puzzle: &Puzzle) -> usize {
+    CubeSet::new(&puzzle.slice, 3).run(6).len()
+}
+
+/// Active cubes after six cycles in four dimensions.
+pub fn part_two(puzzle: &Puzzle) -> usize {
+    CubeSet::new(&puzzle.slice, 4).run(6).len()
+}
+
+/// Parses the puzzle input and solves both parts.
+pub fn solve(input: &str) -> Result<(usize, usize), ParseError> {
+    let puzzle = parse(input)?;
+    Ok((part_one(&puzzle), part_two(&puzzle)))
+}
+
+impl Solution for Puzzle {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        parse(input)
+    }
+
+    fn part_one(&self) -> Answer {
+        part_one(self).into()
+    }
+
+    fn part_two(&self) -> Answer {
+        part_two(self).into()
+    }
+}
+
+/*
+ * .#.
+ * ..#
+ * ###
+ */
+fn parse_slice(input: &str) -> Result<Vec<(i32, i32)>, ParseError> {
+    let mut active = vec![];
+    for (y, line) in input.lines().enumerate() {
+        for (x, ch) in line.char_indices() {
+            match ch {
+                '#' => active.push((x as i32, y as i32)),
+                '.' => {}
+                _ => {
+                    return Err(ParseError::new(
+                        line,
+                        &line[x..],
+                        format!("expected `.` or `#`, found `{}`", ch),
+                    )
+                    .offset(y))
+                }
+            }
+        }
+    }
+    Ok(active)
+}
+
+type Cube = Vec<i32>;
+
+/// A sparse set of active cubes in any number of dimensions.
+struct CubeSet {
+    active: HashSet<Cube>,
+    neighbours: Vec<Cube>,
+}
+
+impl CubeSet {
+    // Places the 2-D `slice` at 0 on every other axis.
+    fn new(slice: &[(i32, i32)], dimensions: usize) -> CubeSet {
+        let active = slice
+            .iter()
+            .map(|&(x, y)| {
+                let mut cube = vec![0; dimensions];
+                cube[0] = x;
+                cube[1] = y;
+                cube
+            })
+            .collect();
+        let neighbours = (0..dimensions)
+            .map(|_| -1..=1)
+            .multi_cartesian_product()
+            .filter(|offset: &Cube| offset.iter().any(|&d| d != 0))
+            .collect();
+        CubeSet { active, neighbours }
+    }
+
+    fn run(mut self, cycles: usize) -> HashSet<Cube> {
+        for _ in 0..cycles {
+            self.step();
+        }
+        self.active
+    }
+
+    // Only cubes next to an active cube can be active after a cycle, so the
+    // neighbours of active cubes are the only ones counted.
+    fn step(&mut self) {
+        let mut counts: HashMap<Cube, usize> = HashMap::new();
+        for cube in self.active.iter() {
+            for offset in self.neighbours.iter() {
+                let neighbour = cube.iter().zip(offset).map(|(c, d)| c + d).collect();
+                *counts.entry(neighbour).or_insert(0) += 1;
+            }
+        }
+        self.active = counts
+            .into_iter()
+            .filter(|(cube, count)| *count == 3 || (*count == 2 && self.active.contains(cube)))
+            .map(|(cube, _)| cube)
+            .collect();
+    }
+}
+
+#[test]
+fn test_glider_three_dimensions() {
+    let slice = parse_slice(".#.\n..#\n###").unwrap();
+    assert_eq!(26, CubeSet::new(&slice, 3).neighbours.len());
+    assert_eq!(11, CubeSet::new(&slice, 3).run(1).len());
+    assert_eq!(112, CubeSet::new(&slice, 3).run(6).len());
+}
+
+#[test]
+fn test_glider_four_dimensions() {
+    let slice = parse_slice(".#.\n..#\n###").unwrap();
+    assert_eq!(80, CubeSet::new(&slice, 4).neighbours.len());
+    assert_eq!(29, CubeSet::new(&slice, 4).run(1).len());
+    assert_eq!(848, CubeSet::new(&slice, 4).run(6).len());
+}
+
+#[test]
+fn test_parse_slice_error() {
+    let err = parse_slice(".#.\n.x#").unwrap_err();
+    assert_eq!((2, 2), (err.line, err.column));
+}
@@ -0,0 +1,293 @@
+//! Day 18: Operation Order
+
+use std::iter::Peekable;
+use std::slice::Iter;
+
+use crate::error::{parse_field, parse_lines, ParseError};
+use crate::solution::{Answer, Solution};
+
+/// The homework: one tokenized expression per line.
+#[derive(Debug)]
+pub struct Puzzle {
+    expressions: Vec<Vec<Token>>,
+}
+
+/// Parses the puzzle input.
+pub fn parse(input: &str) -> Result<Puzzle, ParseError> {
+    Ok(Puzzle {
+        expressions: parse_lines(input, tokenize)?,
+    })
+}
+
+/// Sum of the expressions evaluated with addition and multiplication at the same precedence.
+pub fn part_one(puzzle: &Puzzle) -> Option<u64> {
+    sum_expressions(&puzzle.expressions, &Precedence::EQUAL)
+}
+
+/// Sum of the expressions evaluated with addition before multiplication.
+pub fn part_two(puzzle: &Puzzle) -> Option<u64> {
+    sum_expressions(&puzzle.expressions, &Precedence::ADDITION_FIRST)
+}
+
+/// Parses the puzzle input and solves both parts.
+pub fn solve(input: &str) -> Result<(Option<u64>, Option<u64>), ParseError> {
+    let puzzle = parse(input)?;
+    Ok((part_one(&puzzle), part_two(&puzzle)))
+}
+
+impl Solution for Puzzle {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        parse(input)
+    }
+
+    fn part_one(&self) -> Answer {
+        part_one(self).into()
+    }
+
+    fn part_two(&self) -> Answer {
+        part_two(self).into()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Operator {
+    Add,
+    Multiply,
+}
+
+impl Operator {
+    // None when the result overflows.
+    fn apply(self, lhs: u64, rhs: u64) -> Option<u64> {
+        match self {
+            Operator::Add => lhs.checked_add(rhs),
+            Operator::Multiply => lhs.checked_mul(rhs),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Token {
+    Number(u64),
+    Operator(Operator),
+    Open,
+    Close,
+}
+
+/// Precedence of each operator: the higher binds tighter. Operators of equal
+/// precedence are evaluated left to right.
+#[derive(Debug)]
+pub struct Precedence {
+    pub add: u8,
+    pub multiply: u8,
+}
+
+impl Precedence {
+    pub const EQUAL: Precedence = Precedence {
+        add: 1,
+        multiply: 1,
+    };
+    pub const ADDITION_FIRST: Precedence = Precedence {
+        add: 2,
+        multiply: 1,
+    };
+
+    fn of(&self, operator: Operator) -> u8 {
+        match operator {
+            Operator::Add => self.add,
+            Operator::Multiply => self.multiply,
+        }
+    }
+}
+
+// None when an expression or the sum overflows.
+fn sum_expressions(expressions: &[Vec<Token>], precedence: &Precedence) -> Option<u64> {
+    expressions.iter().try_fold(0u64, |sum, tokens| {
+        sum.checked_add(evaluate(tokens, precedence)?)
+    })
+}
+
+/*
+ * 1 + (2 * 3) + (4 * (5 + 6))
+ */
+/// Splits an expression into tokens, checking that numbers and operators
+/// alternate and that parentheses are balanced.
+pub fn tokenize(line: &str) -> Result<Vec<Token>, ParseError> {
+    let mut tokens = vec![];
+    let mut expect_operand = true;
+    let mut open = vec![];
+    let mut chars = line.char_indices().peekable();
+    while let Some((i, ch)) = chars.next() {
+        let token = match ch {
+            ' ' => continue,
+            '0'..='9' => {
+                let mut end = i + 1;
+                while let Some((j, '0'..='9')) = chars.peek() {
+                    end = j + 1;
+                    chars.next();
+                }
+                Token::Number(parse_field(line, &line[i..end], "a number")?)
+            }
+            '+' => Token::Operator(Operator::Add),
+            '*' => Token::Operator(Operator::Multiply),
+            '(' => Token::Open,
+            ')' => Token::Close,
+            _ => {
+                return Err(ParseError::new(
+                    line,
+                    &line[i..],
+                    format!("unexpected `{}`", ch),
+                ))
+            }
+        };
+        let valid = match token {
+            Token::Number(_) | Token::Open => expect_operand,
+            Token::Operator(_) | Token::Close => !expect_operand,
+        };
+        if !valid {
+            let expected = if expect_operand {
+                "a number or `(`"
+            } else {
+                "an operator or `)`"
+            };
+            return Err(ParseError::new(
+                line,
+                &line[i..],
+                format!("expected {}, found `{}`", expected, ch),
+            ));
+        }
+        match token {
+            Token::Open => open.push(i),
+            Token::Close if open.pop().is_none() => {
+                return Err(ParseError::new(line, &line[i..], "unmatched `)`"))
+            }
+            _ => {}
+        }
+        expect_operand = matches!(token, Token::Operator(_) | Token::Open);
+        tokens.push(token);
+    }
+    if expect_operand {
+        return Err(ParseError::new(
+            line,
+            &line[line.len()..],
+            "expected a number or `(`",
+        ));
+    }
+    if let Some(&i) = open.last() {
+        return Err(ParseError::new(line, &line[i..], "unclosed `(`"));
+    }
+    Ok(tokens)
+}
+
+/// Evaluates well-formed `tokens`, as returned by `tokenize`, by precedence
+/// climbing, or `None` if a result does not fit in a `u64`.
+pub fn evaluate(tokens: &[Token], precedence: &Precedence) -> Option<u64> {
+    evaluate_from(&mut tokens.iter().peekable(), precedence, 0)
+}
+
+// Evaluates operators of at least `min_precedence`, leaving the others for
+// the caller.
+fn evaluate_from(
+    tokens: &mut Peekable<Iter<Token>>,
+    precedence: &Precedence,
+    min_precedence: u8,
+) -> Option<u64> {
+    let mut lhs = evaluate_operand(tokens, precedence)?;
+    while let Some(&&Token::Operator(operator)) = tokens.peek() {
+        let current = precedence.of(operator);
+        if current < min_precedence {
+            break;
+        }
+        tokens.next();
+        let rhs = evaluate_from(tokens, precedence, current + 1)?;
+        lhs = operator.apply(lhs, rhs)?;
+    }
+    Some(lhs)
+}
+
+fn evaluate_operand(tokens: &mut Peekable<Iter<Token>>, precedence: &Precedence) -> Option<u64> {
+    match tokens.next() {
+        Some(Token::Number(n)) => Some(*n),
+        Some(Token::Open) => {
+            let value = evaluate_from(tokens, precedence, 0);
+            assert_eq!(Some(&Token::Close), tokens.next(), "expected `)`");
+            value
+        }
+        token => panic!("expected a number or `(`, found {:?}", token),
+    }
+}
+
+#[cfg(test)]
+fn evaluate_str(line: &str, precedence: &Precedence) -> Option<u64> {
+    evaluate(&tokenize(line).unwrap(), precedence)
+}
+
+#[test]
+fn test_evaluate_equal_precedence() {
+    let p = &Precedence::EQUAL;
+    assert_eq!(Some(71), evaluate_str("1 + 2 * 3 + 4 * 5 + 6", p));
+    assert_eq!(Some(51), evaluate_str("1 + (2 * 3) + (4 * (5 + 6))", p));
+    assert_eq!(Some(26), evaluate_str("2 * 3 + (4 * 5)", p));
+    assert_eq!(Some(437), evaluate_str("5 + (8 * 3 + 9 + 3 * 4 * 3)", p));
+    assert_eq!(
+        Some(12240),
+        evaluate_str("5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))", p)
+    );
+    assert_eq!(
+        Some(13632),
+        evaluate_str("((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2", p)
+    );
+}
+
+#[test]
+fn test_evaluate_addition_first() {
+    let p = &Precedence::ADDITION_FIRST;
+    assert_eq!(Some(231), evaluate_str("1 + 2 * 3 + 4 * 5 + 6", p));
+    assert_eq!(Some(51), evaluate_str("1 + (2 * 3) + (4 * (5 + 6))", p));
+    assert_eq!(Some(46), evaluate_str("2 * 3 + (4 * 5)", p));
+    assert_eq!(Some(1445), evaluate_str("5 + (8 * 3 + 9 + 3 * 4 * 3)", p));
+    assert_eq!(
+        Some(669060),
+        evaluate_str("5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))", p)
+    );
+    assert_eq!(
+        Some(23340),
+        evaluate_str("((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2", p)
+    );
+}
+
+#[test]
+fn test_evaluate_multiplication_first() {
+    let p = &Precedence {
+        add: 1,
+        multiply: 2,
+    };
+    assert_eq!(Some(38), evaluate_str("2 * 3 + 4 * 5 + 12 * 1", p));
+}
+
+#[test]
+fn test_tokenize() {
+    assert_eq!(
+        vec![
+            Token::Number(12),
+            Token::Operator(Operator::Add),
+            Token::Open,
+            Token::Number(3),
+            Token::Close
+        ],
+        tokenize("12 + (3)").unwrap()
+    );
+    assert_eq!(5, tokenize("1 + x").unwrap_err().column);
+    assert_eq!(3, tokenize("1 2").unwrap_err().column);
+    assert_eq!(6, tokenize("1 + 2)").unwrap_err().column);
+    assert_eq!(1, tokenize("(1 + 2").unwrap_err().column);
+    assert_eq!(4, tokenize("1 +").unwrap_err().column);
+}
+
+#[test]
+fn test_evaluate_overflow() {
+    let p = &Precedence::EQUAL;
+    assert_eq!(None, evaluate_str("99999999999999 * 99999999999", p));
+    assert_eq!(None, evaluate_str("(18446744073709551615 + 1) * 0", p));
+    let puzzle = parse("18446744073709551615\n1").unwrap();
+    assert_eq!(None, part_one(&puzzle));
+}
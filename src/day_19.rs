@@ -0,0 +1,235 @@
+//! Day 19: Monster Messages
+
+use std::collections::{HashMap, HashSet};
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::error::{parse_field, ParseError};
+use crate::solution::{Answer, Solution};
+
+lazy_static! {
+    static ref RE_RULE: Regex = Regex::new(r"^(\d+): (.+)$").expect("Invalid regex");
+    static ref RE_CHAR: Regex = Regex::new(r#"^"(\w)"$"#).expect("Invalid regex");
+}
+
+/// The message rules and the received messages.
+#[derive(Debug)]
+pub struct Puzzle {
+    rules: Rules,
+    messages: Vec<String>,
+}
+
+/// Parses the puzzle input.
+pub fn parse(input: &str) -> Result<Puzzle, ParseError> {
+    let (rules, messages) = parse_rules_and_messages(input)?;
+    Ok(Puzzle { rules, messages })
+}
+
+/// Number of messages that completely match rule 0.
+pub fn part_one(puzzle: &Puzzle) -> usize {
+    count_matches(&puzzle.rules, &puzzle.messages)
+}
+
+/// Number of messages that completely match rule 0 once rules 8 and 11 loop.
+pub fn part_two(puzzle: &Puzzle) -> usize {
+    let mut rules = puzzle.rules.clone();
+    rules.insert(8, Rule::Sequences(vec![vec![42], vec![42, 8]]));
+    rules.insert(11, Rule::Sequences(vec![vec![42, 31], vec![42, 11, 31]]));
+    count_matches(&rules, &puzzle.messages)
+}
+
+/// Parses the puzzle input and solves both parts.
+pub fn solve(input: &str) -> Result<(usize, usize), ParseError> {
+    let puzzle = parse(input)?;
+    Ok((part_one(&puzzle), part_two(&puzzle)))
+}
+
+impl Solution for Puzzle {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        parse(input)
+    }
+
+    fn part_one(&self) -> Answer {
+        part_one(self).into()
+    }
+
+    fn part_two(&self) -> Answer {
+        part_two(self).into()
+    }
+}
+
+/// A rule matches either a single char, or any of its sequences of rules.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Rule {
+    Char(char),
+    Sequences(Vec<Vec<usize>>),
+}
+
+pub type Rules = HashMap<usize, Rule>;
+
+fn count_matches(rules: &Rules, messages: &[String]) -> usize {
+    messages.iter().filter(|m| matches(rules, m)).count()
+}
+
+/// Whether the whole `message` matches rule 0.
+pub fn matches(rules: &Rules, message: &str) -> bool {
+    let message: Vec<char> = message.chars().collect();
+    matches_sequence(rules, &message, &[0])
+}
+
+// Whether `message` matches the rules of `sequence` one after the other. Each
+// alternative is tried in turn, so a rule may refer to itself: every rule
+// matches at least one char, and no rule leads back to itself through
+// single-rule alternatives, so every few steps either a char is matched or the
+// sequence grows, which bounds the recursion by the message length.
+fn matches_sequence(rules: &Rules, message: &[char], sequence: &[usize]) -> bool {
+    if sequence.len() > message.len() {
+        return false;
+    }
+    let (first, rest) = match sequence.split_first() {
+        Some(split) => split,
+        None => return message.is_empty(),
+    };
+    match rules.get(first) {
+        Some(Rule::Char(ch)) => message[0] == *ch && matches_sequence(rules, &message[1..], rest),
+        Some(Rule::Sequences(sequences)) => sequences.iter().any(|s| {
+            let expanded: Vec<usize> = s.iter().chain(rest).copied().collect();
+            matches_sequence(rules, message, &expanded)
+        }),
+        None => false,
+    }
+}
+
+/*
+ * 0: 1 2
+ * 1: "a"
+ * 2: 1 3 | 3 1
+ * 3: "b"
+ *
+ * aab
+ */
+fn parse_rules_and_messages(input: &str) -> Result<(Rules, Vec<String>), ParseError> {
+    let mut lines = input.lines().enumerate();
+    let mut rules = HashMap::new();
+    let mut references = vec![];
+    let mut definitions = vec![];
+    for (i, line) in lines.by_ref().take_while(|(_, l)| !l.is_empty()) {
+        let groups = RE_RULE
+            .captures(line)
+            .ok_or_else(|| ParseError::new(line, line, "expected `<number>: <rule>`").offset(i))?;
+        let number = parse_field(line, &groups[1], "a rule number").map_err(|e| e.offset(i))?;
+        let body = groups.get(2).expect("expected capture group").as_str();
+        let rule = if let Some(ch) = RE_CHAR.captures(body) {
+            Rule::Char(parse_field(line, &ch[1], "a char").map_err(|e| e.offset(i))?)
+        } else {
+            let mut sequences = vec![];
+            for sequence in body.split('|') {
+                let mut numbers = vec![];
+                for field in sequence.split_whitespace() {
+                    let n = parse_field(line, field, "a rule number").map_err(|e| e.offset(i))?;
+                    references.push((i, line, field, n));
+                    numbers.push(n);
+                }
+                // An empty alternative would match no char at all
+                if numbers.is_empty() {
+                    let message = "expected rule numbers in every alternative";
+                    return Err(ParseError::new(line, sequence, message).offset(i));
+                }
+                sequences.push(numbers);
+            }
+            Rule::Sequences(sequences)
+        };
+        if rules.insert(number, rule).is_some() {
+            return Err(ParseError::new(line, line, "duplicate rule").offset(i));
+        }
+        definitions.push((i, line, number));
+    }
+    for (i, line, field, n) in references {
+        if !rules.contains_key(&n) {
+            return Err(ParseError::new(line, field, format!("unknown rule {}", n)).offset(i));
+        }
+    }
+    for (i, line, number) in definitions {
+        if is_unit_cycle(&rules, number) {
+            let message = format!(
+                "rule {} refers back to itself without matching a char",
+                number
+            );
+            return Err(ParseError::new(line, line, message).offset(i));
+        }
+    }
+    if !rules.contains_key(&0) {
+        return Err(ParseError::new("", "", "expected a rule 0"));
+    }
+
+    let messages = lines.map(|(_, l)| l.to_string()).collect();
+    Ok((rules, messages))
+}
+
+// Whether `start` can be replaced by itself through alternatives made of a
+// single rule, which would expand forever without matching a char.
+fn is_unit_cycle(rules: &Rules, start: usize) -> bool {
+    let mut seen = HashSet::new();
+    let mut stack = vec![start];
+    while let Some(number) = stack.pop() {
+        if let Some(Rule::Sequences(sequences)) = rules.get(&number) {
+            for sequence in sequences.iter().filter(|s| s.len() == 1) {
+                if sequence[0] == start {
+                    return true;
+                }
+                if seen.insert(sequence[0]) {
+                    stack.push(sequence[0]);
+                }
+            }
+        }
+    }
+    false
+}
+
+#[test]
+fn test_matches() {
+    let input = "0: 4 1 5\n1: 2 3 | 3 2\n2: 4 4 | 5 5\n3: 4 5 | 5 4\n4: \"a\"\n5: \"b\"\n\nababbb\nbababa\nabbbab\naaabbb\naaaabbb";
+    let puzzle = parse(input).unwrap();
+    let matched: Vec<bool> = puzzle
+        .messages
+        .iter()
+        .map(|m| matches(&puzzle.rules, m))
+        .collect();
+    assert_eq!(vec![true, false, true, false, false], matched);
+}
+
+#[test]
+fn test_matches_looping_rules() {
+    let input = "42: 9 14 | 10 1\n9: 14 27 | 1 26\n10: 23 14 | 28 1\n1: \"a\"\n11: 42 31\n5: 1 14 | 15 1\n19: 14 1 | 14 14\n12: 24 14 | 19 1\n16: 15 1 | 14 14\n31: 14 17 | 1 13\n6: 14 14 | 1 14\n2: 1 24 | 14 4\n0: 8 11\n13: 14 3 | 1 12\n15: 1 | 14\n17: 14 2 | 1 7\n23: 25 1 | 22 14\n28: 16 1\n4: 1 1\n20: 14 14 | 1 15\n3: 5 14 | 16 1\n27: 1 6 | 14 18\n14: \"b\"\n21: 14 1 | 1 14\n25: 1 1 | 1 14\n22: 14 14\n8: 42\n26: 14 22 | 1 20\n18: 15 15\n7: 14 5 | 1 21\n24: 14 1\n\nabbbbbabbbaaaababbaabbbbabababbbabbbbbbabaaaa\nbbabbbbaabaabba\nbabbbbaabbbbbabbbbbbaabaaabaaa\naaabbbbbbaaaabaababaabababbabaaabbababababaaa\nbbbbbbbaaaabbbbaaabbabaaa\nbbbababbbbaaaaaaaabbababaaababaabab\nababaaaaaabaaab\nababaaaaabbbaba\nbaabbaaaabbaaaababbaababb\nabbbbabbbbaaaababbbbbbaaaababb\naaaaabbaabaaaaababaa\naaaabbaaaabbaaa\naaaabbaabbaaaaaaabbbabbbaaabbaabaaa\nbabaaabbbaaabaababbaabababaaab\naabbbbbaabbbaaaaaabbbbbababaaaaabbaaabba";
+    let puzzle = parse(input).unwrap();
+    assert_eq!(3, part_one(&puzzle));
+    assert_eq!(12, part_two(&puzzle));
+}
+
+#[test]
+fn test_parse_unknown_rule() {
+    let err = parse("0: 1 2\n1: \"a\"\n\na").unwrap_err();
+    assert_eq!((1, 6), (err.line, err.column));
+    assert_eq!("unknown rule 2", err.message);
+}
+
+#[test]
+fn test_parse_empty_alternative() {
+    let err = parse("8: | 8\n0: 8\n\na").unwrap_err();
+    assert_eq!((1, 4), (err.line, err.column));
+    assert_eq!("expected rule numbers in every alternative", err.message);
+    assert!(parse("0: 1 |\n1: \"a\"\n\na").is_err());
+}
+
+#[test]
+fn test_parse_unit_cycle() {
+    let err = parse("0: 1\n1: 0 | 2\n2: \"a\"\n\na").unwrap_err();
+    assert_eq!(1, err.line);
+    assert_eq!(
+        "rule 0 refers back to itself without matching a char",
+        err.message
+    );
+    assert!(parse("0: 0\n\na").is_err());
+    assert!(parse("0: 1 | 1 0\n1: \"a\"\n\na").is_ok());
+}
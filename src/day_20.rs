@@ -0,0 +1,324 @@
+//! Day 20: Jurassic Jigsaw
+
+use std::collections::{HashMap, HashSet};
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::error::{parse_field, ParseError};
+use crate::grid::Grid;
+use crate::solution::{Answer, Solution};
+
+lazy_static! {
+    static ref RE: Regex = Regex::new(r"^Tile (\d+):$").expect("Invalid regex");
+}
+
+/// The image tiles.
+#[derive(Debug)]
+pub struct Puzzle {
+    tiles: Vec<Tile>,
+}
+
+/// Parses the puzzle input.
+pub fn parse(input: &str) -> Result<Puzzle, ParseError> {
+    Ok(Puzzle {
+        tiles: parse_tiles(input)?,
+    })
+}
+
+/// Product of the ids of the four corner tiles.
+pub fn part_one(puzzle: &Puzzle) -> Option<u64> {
+    let corners = find_corners(&puzzle.tiles);
+    if corners.len() != 4 {
+        return None;
+    }
+    Some(corners.iter().map(|&i| puzzle.tiles[i].id).product())
+}
+
+/// Number of `#` in the assembled image that are not part of a sea monster.
+pub fn part_two(puzzle: &Puzzle) -> Option<usize> {
+    let image = assemble(&puzzle.tiles)?;
+    image.orientations().iter().find_map(count_rough_waters)
+}
+
+/// Parses the puzzle input and solves both parts.
+pub fn solve(input: &str) -> Result<(Option<u64>, Option<usize>), ParseError> {
+    let puzzle = parse(input)?;
+    Ok((part_one(&puzzle), part_two(&puzzle)))
+}
+
+impl Solution for Puzzle {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        parse(input)
+    }
+
+    fn part_one(&self) -> Answer {
+        part_one(self).into()
+    }
+
+    fn part_two(&self) -> Answer {
+        part_two(self).into()
+    }
+}
+
+#[derive(Debug)]
+struct Tile {
+    id: u64,
+    image: Grid<bool>,
+}
+
+type Edge = Vec<bool>;
+
+impl Tile {
+    // Top, bottom, left and right edges, each read in the same direction as
+    // the edge of a neighbour matching it would be.
+    fn edges(&self) -> [Edge; 4] {
+        let image = &self.image;
+        [
+            image.row(0).to_vec(),
+            image.row(image.height() - 1).to_vec(),
+            image.column(0),
+            image.column(image.width() - 1),
+        ]
+    }
+}
+
+/*
+ * Tile 2311:
+ * ..##.#..#.
+ * ##..#.....
+ * ...
+ */
+fn parse_tiles(input: &str) -> Result<Vec<Tile>, ParseError> {
+    let lines: Vec<&str> = input.lines().collect();
+    let mut tiles: Vec<Tile> = vec![];
+    let mut offset = 0;
+    for block in lines.split(|line| line.is_empty()) {
+        if !block.is_empty() {
+            let tile = parse_tile(block).map_err(|e| e.offset(offset))?;
+            let size = tiles
+                .first()
+                .map_or(tile.image.width(), |t| t.image.width());
+            if tile.image.width() != size || tile.image.height() != size || size < 2 {
+                return Err(ParseError::new(
+                    block[0],
+                    block[0],
+                    format!("expected a tile of {}x{} pixels", size, size),
+                )
+                .offset(offset));
+            }
+            tiles.push(tile);
+        }
+        offset += block.len() + 1;
+    }
+    Ok(tiles)
+}
+
+fn parse_tile(block: &[&str]) -> Result<Tile, ParseError> {
+    let header = block[0];
+    let groups = RE
+        .captures(header)
+        .ok_or_else(|| ParseError::new(header, header, "expected `Tile <id>:`"))?;
+    let pixel = |c| match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    };
+    let id = parse_field(header, &groups[1], "a tile id")?;
+    let image = Grid::parse(&block[1..].join("\n"), pixel, "`.` or `#`");
+    Ok(Tile {
+        id,
+        image: image.map_err(|e| e.offset(1))?,
+    })
+}
+
+// An edge and its reverse are the same edge, seen from either side.
+fn canonical(edge: &[bool]) -> Edge {
+    let reversed: Edge = edge.iter().rev().copied().collect();
+    reversed.min(edge.to_vec())
+}
+
+// Indexes of the tiles with two edges that match no other tile. Edges are
+// unique in the puzzle input, so those are the corners of the image.
+fn find_corners(tiles: &[Tile]) -> Vec<usize> {
+    let mut counts: HashMap<Edge, usize> = HashMap::new();
+    for tile in tiles {
+        for edge in tile.edges().iter() {
+            *counts.entry(canonical(edge)).or_insert(0) += 1;
+        }
+    }
+    (0..tiles.len())
+        .filter(|&i| {
+            let edges = tiles[i].edges();
+            edges.iter().filter(|e| counts[&canonical(e)] == 1).count() == 2
+        })
+        .collect()
+}
+
+// Arranges the tiles in a square, starting from a corner, and joins them
+// without their borders.
+fn assemble(tiles: &[Tile]) -> Option<Grid<bool>> {
+    let size = (1..=tiles.len()).find(|s| s * s >= tiles.len())?;
+    if size * size != tiles.len() {
+        return None;
+    }
+    let orientations: Vec<Vec<Grid<bool>>> = tiles.iter().map(|t| t.image.orientations()).collect();
+    let mut arrangement = Arrangement {
+        orientations: &orientations,
+        size,
+        used: vec![false; tiles.len()],
+        placed: vec![],
+    };
+    let corners = find_corners(tiles);
+    if !corners.iter().any(|&c| arrangement.place_at_start(c)) {
+        return None;
+    }
+
+    let cropped: Vec<Grid<bool>> = arrangement
+        .placed
+        .iter()
+        .map(|&(tile, orientation)| orientations[tile][orientation].crop(1))
+        .collect();
+    let width = cropped[0].width();
+    Some(Grid::from_fn(size * width, size * width, |x, y| {
+        *cropped[(y / width) * size + x / width].get(x % width, y % width)
+    }))
+}
+
+// Backtracking placement of the tiles row by row, each tile in one of its
+// orientations, matching the tiles on its left and above.
+struct Arrangement<'a> {
+    orientations: &'a [Vec<Grid<bool>>],
+    size: usize,
+    used: Vec<bool>,
+    placed: Vec<(usize, usize)>,
+}
+
+impl<'a> Arrangement<'a> {
+    fn place_at_start(&mut self, tile: usize) -> bool {
+        (0..self.orientations[tile].len()).any(|o| self.try_place(tile, o))
+    }
+
+    fn place_next(&mut self) -> bool {
+        if self.placed.len() == self.size * self.size {
+            return true;
+        }
+        for tile in 0..self.orientations.len() {
+            if self.used[tile] {
+                continue;
+            }
+            for orientation in 0..self.orientations[tile].len() {
+                if self.fits(&self.orientations[tile][orientation])
+                    && self.try_place(tile, orientation)
+                {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    fn try_place(&mut self, tile: usize, orientation: usize) -> bool {
+        self.used[tile] = true;
+        self.placed.push((tile, orientation));
+        if self.place_next() {
+            return true;
+        }
+        self.placed.pop();
+        self.used[tile] = false;
+        false
+    }
+
+    fn fits(&self, image: &Grid<bool>) -> bool {
+        let position = self.placed.len();
+        let grid = |index: usize| {
+            let (tile, orientation) = self.placed[index];
+            &self.orientations[tile][orientation]
+        };
+        if !position.is_multiple_of(self.size) {
+            let left = grid(position - 1);
+            if left.column(left.width() - 1) != image.column(0) {
+                return false;
+            }
+        }
+        if position >= self.size {
+            let above = grid(position - self.size);
+            if above.row(above.height() - 1) != image.row(0) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+const SEA_MONSTER: [&str; 3] = [
+    "                  # ",
+    "#    ##    ##    ###",
+    " #  #  #  #  #  #   ",
+];
+
+// Number of `#` that are not part of a sea monster, if `image` is oriented so
+// that sea monsters can be seen.
+fn count_rough_waters(image: &Grid<bool>) -> Option<usize> {
+    let monster: Vec<(usize, usize)> = SEA_MONSTER
+        .iter()
+        .enumerate()
+        .flat_map(|(y, row)| {
+            row.char_indices()
+                .filter(|(_, c)| *c == '#')
+                .map(move |(x, _)| (x, y))
+        })
+        .collect();
+    let monster_width = SEA_MONSTER[0].len();
+    let monster_height = SEA_MONSTER.len();
+    if image.width() < monster_width || image.height() < monster_height {
+        return None;
+    }
+
+    let mut covered = HashSet::new();
+    for y in 0..=image.height() - monster_height {
+        for x in 0..=image.width() - monster_width {
+            if monster.iter().all(|&(dx, dy)| *image.get(x + dx, y + dy)) {
+                covered.extend(monster.iter().map(|&(dx, dy)| (x + dx, y + dy)));
+            }
+        }
+    }
+    if covered.is_empty() {
+        return None;
+    }
+    Some(image.cells().filter(|&&pixel| pixel).count() - covered.len())
+}
+
+#[cfg(test)]
+const EXAMPLE: &str = "Tile 2311:\n..##.#..#.\n##..#.....\n#...##..#.\n####.#...#\n##.##.###.\n##...#.###\n.#.#.#..##\n..#....#..\n###...#.#.\n..###..###\n\nTile 1951:\n#.##...##.\n#.####...#\n.....#..##\n#...######\n.##.#....#\n.###.#####\n###.##.##.\n.###....#.\n..#.#..#.#\n#...##.#..\n\nTile 1171:\n####...##.\n#..##.#..#\n##.#..#.#.\n.###.####.\n..###.####\n.##....##.\n.#...####.\n#.##.####.\n####..#...\n.....##...\n\nTile 1427:\n###.##.#..\n.#..#.##..\n.#.##.#..#\n#.#.#.##.#\n....#...##\n...##..##.\n...#.#####\n.#.####.#.\n..#..###.#\n..##.#..#.\n\nTile 1489:\n##.#.#....\n..##...#..\n.##..##...\n..#...#...\n#####...#.\n#..#.#.#.#\n...#.#.#..\n##.#...##.\n..##.##.##\n###.##.#..\n\nTile 2473:\n#....####.\n#..#.##...\n#.##..#...\n######.#.#\n.#...#.#.#\n.#########\n.###.#..#.\n########.#\n##...##.#.\n..###.#.#.\n\nTile 2971:\n..#.#....#\n#...###...\n#.#.###...\n##.##..#..\n.#####..##\n.#..####.#\n#..#.#..#.\n..####.###\n..#.#.###.\n...#.#.#.#\n\nTile 2729:\n...#.#.#.#\n####.#....\n..#.#.....\n....#..#.#\n.##..##.#.\n.#.####...\n####.#.#..\n##.####...\n##..#.##..\n#.##...##.\n\nTile 3079:\n#.#.#####.\n.#..######\n..#.......\n######....\n####.#..#.\n.#...#.##.\n#.#####.##\n..#.###...\n..#.......\n..#.###...";
+
+#[test]
+fn test_find_corners() {
+    let puzzle = parse(EXAMPLE).unwrap();
+    assert_eq!(Some(20899048083289), part_one(&puzzle));
+}
+
+#[test]
+fn test_assemble_and_find_sea_monsters() {
+    let puzzle = parse(EXAMPLE).unwrap();
+    let image = assemble(&puzzle.tiles).unwrap();
+    assert_eq!((24, 24), (image.width(), image.height()));
+    assert_eq!(Some(273), part_two(&puzzle));
+}
+
+#[test]
+fn test_parse_tiles_errors() {
+    let err = parse("Tile 1:\n#.\n.#\n\nTile 2:\n#.\n.x").unwrap_err();
+    assert_eq!((7, 2), (err.line, err.column));
+    let err = parse("Tile 1:\n#.\n.#\n\nTile 2:\n#..\n.#.\n...").unwrap_err();
+    assert_eq!("expected a tile of 2x2 pixels", err.message);
+    assert_eq!(5, err.line);
+}
+
+#[test]
+fn test_count_rough_waters_small_image() {
+    let image = Grid::from_fn(12, 12, |_, _| true);
+    assert_eq!(None, count_rough_waters(&image));
+    let image = Grid::from_fn(30, 2, |_, _| true);
+    assert_eq!(None, count_rough_waters(&image));
+}
@@ -0,0 +1,149 @@
+//! Day 21: Allergen Assessment
+
+use std::collections::{BTreeMap, HashSet};
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::error::{parse_lines, ParseError};
+use crate::solution::{Answer, Solution};
+
+lazy_static! {
+    static ref RE: Regex =
+        Regex::new(r"^([a-z]+(?: [a-z]+)*)(?: \(contains ([a-z]+(?:, [a-z]+)*)\))?$")
+            .expect("Invalid regex");
+}
+
+/// The list of foods.
+#[derive(Debug)]
+pub struct Puzzle {
+    foods: Vec<Food>,
+}
+
+/// Parses the puzzle input.
+pub fn parse(input: &str) -> Result<Puzzle, ParseError> {
+    Ok(Puzzle {
+        foods: parse_lines(input, parse_food)?,
+    })
+}
+
+/// Number of times ingredients that cannot contain any allergen appear.
+pub fn part_one(puzzle: &Puzzle) -> usize {
+    let candidates = find_candidates(&puzzle.foods);
+    let unsafe_ingredients: HashSet<&str> = candidates.values().flatten().copied().collect();
+    puzzle
+        .foods
+        .iter()
+        .flat_map(|food| food.ingredients.iter())
+        .filter(|ingredient| !unsafe_ingredients.contains(ingredient.as_str()))
+        .count()
+}
+
+/// The dangerous ingredients, sorted by their allergen and separated by commas.
+pub fn part_two(puzzle: &Puzzle) -> Option<String> {
+    let allergens = assign_allergens(find_candidates(&puzzle.foods))?;
+    Some(allergens.values().copied().collect::<Vec<&str>>().join(","))
+}
+
+/// Parses the puzzle input and solves both parts.
+pub fn solve(input: &str) -> Result<(usize, Option<String>), ParseError> {
+    let puzzle = parse(input)?;
+    Ok((part_one(&puzzle), part_two(&puzzle)))
+}
+
+impl Solution for Puzzle {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        parse(input)
+    }
+
+    fn part_one(&self) -> Answer {
+        part_one(self).into()
+    }
+
+    fn part_two(&self) -> Answer {
+        part_two(self).into()
+    }
+}
+
+#[derive(Debug)]
+struct Food {
+    ingredients: Vec<String>,
+    allergens: Vec<String>,
+}
+
+/*
+ * mxmxvkd kfcds sqjhc nhms (contains dairy, fish)
+ */
+fn parse_food(line: &str) -> Result<Food, ParseError> {
+    let groups = RE.captures(line).ok_or_else(|| {
+        ParseError::new(
+            line,
+            line,
+            "expected `<ingredients> (contains <allergens>)`",
+        )
+    })?;
+    Ok(Food {
+        ingredients: groups[1].split(' ').map(|s| s.to_string()).collect(),
+        allergens: groups.get(2).map_or(vec![], |g| {
+            g.as_str().split(", ").map(|s| s.to_string()).collect()
+        }),
+    })
+}
+
+// For each allergen, the ingredients found in every food that contains it.
+fn find_candidates(foods: &[Food]) -> BTreeMap<&str, HashSet<&str>> {
+    let mut candidates: BTreeMap<&str, HashSet<&str>> = BTreeMap::new();
+    for food in foods {
+        let ingredients: HashSet<&str> = food.ingredients.iter().map(|s| s.as_str()).collect();
+        for allergen in food.allergens.iter() {
+            candidates
+                .entry(allergen)
+                .and_modify(|c| c.retain(|i| ingredients.contains(i)))
+                .or_insert_with(|| ingredients.clone());
+        }
+    }
+    candidates
+}
+
+// Assigns each allergen its ingredient by elimination: an allergen with a
+// single candidate gets it, which then cannot contain any other allergen.
+// Returns `None` if the elimination gets stuck.
+fn assign_allergens<'a>(
+    mut candidates: BTreeMap<&'a str, HashSet<&'a str>>,
+) -> Option<BTreeMap<&'a str, &'a str>> {
+    let mut allergens = BTreeMap::new();
+    while !candidates.is_empty() {
+        let (&allergen, ingredients) = candidates.iter().find(|(_, c)| c.len() == 1)?;
+        let ingredient = *ingredients.iter().next()?;
+        candidates.remove(allergen);
+        for c in candidates.values_mut() {
+            c.remove(ingredient);
+        }
+        allergens.insert(allergen, ingredient);
+    }
+    Some(allergens)
+}
+
+#[cfg(test)]
+const EXAMPLE: &str = "mxmxvkd kfcds sqjhc nhms (contains dairy, fish)\ntrh fvjkl sbzzf mxmxvkd (contains dairy)\nsqjhc fvjkl (contains soy)\nsqjhc mxmxvkd sbzzf (contains fish)";
+
+#[test]
+fn test_count_safe_ingredients() {
+    assert_eq!(5, part_one(&parse(EXAMPLE).unwrap()));
+}
+
+#[test]
+fn test_dangerous_ingredients() {
+    assert_eq!(
+        Some("mxmxvkd,sqjhc,fvjkl".to_string()),
+        part_two(&parse(EXAMPLE).unwrap())
+    );
+}
+
+#[test]
+fn test_parse_food() {
+    let food = parse_food("sqjhc fvjkl").unwrap();
+    assert_eq!(vec!["sqjhc", "fvjkl"], food.ingredients);
+    assert!(food.allergens.is_empty());
+    assert!(parse_food("sqjhc (contains)").is_err());
+}
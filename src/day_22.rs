@@ -0,0 +1,275 @@
+//! Day 22: Crab Combat
+
+use std::collections::{HashSet, VecDeque};
+use std::io::Write;
+
+use crate::error::{parse_field, ParseError};
+use crate::solution::{Answer, Part, Solution};
+
+/// The starting decks of both players.
+#[derive(Debug)]
+pub struct Puzzle {
+    decks: [Deck; 2],
+}
+
+/// Parses the puzzle input.
+pub fn parse(input: &str) -> Result<Puzzle, ParseError> {
+    Ok(Puzzle {
+        decks: parse_decks(input)?,
+    })
+}
+
+/// Score of the winner of a game of Combat, if the game ends.
+pub fn part_one(puzzle: &Puzzle) -> Option<usize> {
+    part_one_verbose(puzzle, None)
+}
+
+/// Score of the winner of a game of Recursive Combat.
+pub fn part_two(puzzle: &Puzzle) -> usize {
+    part_two_verbose(puzzle, None)
+}
+
+/// Same as `part_one`, printing every round to `trace` like the puzzle text.
+pub fn part_one_verbose(puzzle: &Puzzle, trace: Option<&mut dyn Write>) -> Option<usize> {
+    let mut game = Game { trace, games: 0 };
+    let (winner, decks) = game.combat(puzzle.decks.clone())?;
+    game.post_game(&decks);
+    Some(score(&decks[winner]))
+}
+
+/// Same as `part_two`, printing every round of every sub-game to `trace` like
+/// the puzzle text.
+pub fn part_two_verbose(puzzle: &Puzzle, trace: Option<&mut dyn Write>) -> usize {
+    let mut game = Game { trace, games: 0 };
+    let (winner, decks) = game.recursive_combat(puzzle.decks.clone());
+    game.post_game(&decks);
+    score(&decks[winner])
+}
+
+/// Parses the puzzle input and solves both parts.
+pub fn solve(input: &str) -> Result<(Option<usize>, usize), ParseError> {
+    let puzzle = parse(input)?;
+    Ok((part_one(&puzzle), part_two(&puzzle)))
+}
+
+impl Solution for Puzzle {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        parse(input)
+    }
+
+    fn part_one(&self) -> Answer {
+        part_one(self).into()
+    }
+
+    fn part_two(&self) -> Answer {
+        part_two(self).into()
+    }
+
+    fn solve_verbose(&self, part: Part, out: &mut dyn Write) -> Answer {
+        match part {
+            Part::One => part_one_verbose(self, Some(out)).into(),
+            Part::Two => part_two_verbose(self, Some(out)).into(),
+        }
+    }
+}
+
+type Deck = VecDeque<usize>;
+
+/*
+ * Player 1:
+ * 9
+ * 2
+ *
+ * Player 2:
+ * 5
+ * 8
+ */
+fn parse_decks(input: &str) -> Result<[Deck; 2], ParseError> {
+    let lines: Vec<&str> = input.lines().collect();
+    let mut blocks = lines.split(|line| line.is_empty());
+    let mut decks = [Deck::new(), Deck::new()];
+    let mut offset = 0;
+    for (player, deck) in decks.iter_mut().enumerate() {
+        let block = blocks.next().unwrap_or(&[]);
+        let header = block.first().unwrap_or(&"");
+        let expected = format!("Player {}:", player + 1);
+        if *header != expected {
+            let message = format!("expected `{}`", expected);
+            return Err(ParseError::new(header, header, message).offset(offset));
+        }
+        for (i, line) in block.iter().enumerate().skip(1) {
+            let card = parse_field(line, line, "a card").map_err(|e| e.offset(offset + i))?;
+            deck.push_back(card);
+        }
+        offset += block.len() + 1;
+    }
+    Ok(decks)
+}
+
+fn score(deck: &Deck) -> usize {
+    deck.iter()
+        .rev()
+        .enumerate()
+        .map(|(i, card)| (i + 1) * card)
+        .sum()
+}
+
+fn format_deck(deck: &Deck) -> String {
+    deck.iter()
+        .map(|card| card.to_string())
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+// Writes a line to the trace of a game, if any.
+macro_rules! trace {
+    ($game:expr, $($arg:tt)*) => {
+        if let Some(out) = $game.trace.as_mut() {
+            writeln!(out, $($arg)*).expect("Unable to write trace");
+        }
+    };
+}
+
+struct Game<'a> {
+    trace: Option<&'a mut dyn Write>,
+    games: usize,
+}
+
+impl<'a> Game<'a> {
+    // Plays until a deck is empty, returning the index of the winner, or None
+    // when the decks come back to those of an earlier round and the game
+    // would never end.
+    fn combat(&mut self, mut decks: [Deck; 2]) -> Option<(usize, [Deck; 2])> {
+        let mut seen = HashSet::new();
+        let mut round = 0;
+        while !decks[0].is_empty() && !decks[1].is_empty() {
+            if !seen.insert(decks.clone()) {
+                trace!(self, "Decks seen before, the game never ends!");
+                return None;
+            }
+            round += 1;
+            trace!(self, "-- Round {} --", round);
+            let cards = self.draw(&mut decks);
+            let winner = if cards[0] > cards[1] { 0 } else { 1 };
+            trace!(self, "Player {} wins the round!\n", winner + 1);
+            decks[winner].push_back(cards[winner]);
+            decks[winner].push_back(cards[1 - winner]);
+        }
+        let winner = if decks[0].is_empty() { 1 } else { 0 };
+        Some((winner, decks))
+    }
+
+    // Plays until a deck is empty, or until the decks are the same as in an
+    // earlier round of this game, in which case player 1 wins.
+    fn recursive_combat(&mut self, mut decks: [Deck; 2]) -> (usize, [Deck; 2]) {
+        self.games += 1;
+        let game = self.games;
+        trace!(self, "=== Game {} ===\n", game);
+
+        let mut seen = HashSet::new();
+        let mut round = 0;
+        let winner = loop {
+            if decks[0].is_empty() {
+                break 1;
+            }
+            if decks[1].is_empty() {
+                break 0;
+            }
+            if !seen.insert(decks.clone()) {
+                trace!(self, "Decks seen before in game {}!", game);
+                break 0;
+            }
+            round += 1;
+            trace!(self, "-- Round {} (Game {}) --", round, game);
+            let cards = self.draw(&mut decks);
+            let winner = if decks[0].len() >= cards[0] && decks[1].len() >= cards[1] {
+                trace!(self, "Playing a sub-game to determine the winner...\n");
+                let sub_decks = [
+                    decks[0].iter().take(cards[0]).copied().collect(),
+                    decks[1].iter().take(cards[1]).copied().collect(),
+                ];
+                let (winner, _) = self.recursive_combat(sub_decks);
+                trace!(self, "...anyway, back to game {}.", game);
+                winner
+            } else if cards[0] > cards[1] {
+                0
+            } else {
+                1
+            };
+            trace!(
+                self,
+                "Player {} wins round {} of game {}!\n",
+                winner + 1,
+                round,
+                game
+            );
+            decks[winner].push_back(cards[winner]);
+            decks[winner].push_back(cards[1 - winner]);
+        };
+        trace!(
+            self,
+            "The winner of game {} is player {}!\n",
+            game,
+            winner + 1
+        );
+        (winner, decks)
+    }
+
+    fn draw(&mut self, decks: &mut [Deck; 2]) -> [usize; 2] {
+        trace!(self, "Player 1's deck: {}", format_deck(&decks[0]));
+        trace!(self, "Player 2's deck: {}", format_deck(&decks[1]));
+        let cards = [
+            decks[0].pop_front().expect("expected a card"),
+            decks[1].pop_front().expect("expected a card"),
+        ];
+        trace!(self, "Player 1 plays: {}", cards[0]);
+        trace!(self, "Player 2 plays: {}", cards[1]);
+        cards
+    }
+
+    fn post_game(&mut self, decks: &[Deck; 2]) {
+        trace!(self, "== Post-game results ==");
+        trace!(self, "Player 1's deck: {}", format_deck(&decks[0]));
+        trace!(self, "Player 2's deck: {}", format_deck(&decks[1]));
+    }
+}
+
+#[cfg(test)]
+const EXAMPLE: &str = "Player 1:\n9\n2\n6\n3\n1\n\nPlayer 2:\n5\n8\n4\n7\n10";
+
+#[test]
+fn test_combat() {
+    let puzzle = parse(EXAMPLE).unwrap();
+    assert_eq!(Some(306), part_one(&puzzle));
+}
+
+#[test]
+fn test_recursive_combat() {
+    let puzzle = parse(EXAMPLE).unwrap();
+    assert_eq!(291, part_two(&puzzle));
+}
+
+#[test]
+fn test_recursive_combat_loop() {
+    let puzzle = parse("Player 1:\n43\n19\n\nPlayer 2:\n2\n29\n14").unwrap();
+    assert_eq!(105, part_two(&puzzle));
+    assert_eq!(None, part_one(&puzzle));
+}
+
+#[test]
+fn test_trace() {
+    let puzzle = parse(EXAMPLE).unwrap();
+    let mut trace = vec![];
+    assert_eq!(291, part_two_verbose(&puzzle, Some(&mut trace)));
+    let trace = String::from_utf8(trace).unwrap();
+    assert!(trace.starts_with("=== Game 1 ===\n\n-- Round 1 (Game 1) --\nPlayer 1's deck: 9, 2, 6, 3, 1\nPlayer 2's deck: 5, 8, 4, 7, 10\nPlayer 1 plays: 9\nPlayer 2 plays: 5\nPlayer 1 wins round 1 of game 1!\n"));
+    assert!(trace.contains("Playing a sub-game to determine the winner...\n\n=== Game 2 ===\n"));
+    assert!(trace.ends_with("== Post-game results ==\nPlayer 1's deck: \nPlayer 2's deck: 7, 5, 6, 2, 4, 1, 10, 8, 9, 3\n"));
+}
+
+#[test]
+fn test_parse_decks_error() {
+    let err = parse("Player 1:\n9\n\nPlayer 3:\n5").unwrap_err();
+    assert_eq!(4, err.line);
+    assert_eq!("expected `Player 2:`", err.message);
+}
@@ -0,0 +1,189 @@
+//! Day 23: Crab Cups
+
+use crate::error::ParseError;
+use crate::solution::{Answer, Solution};
+
+/// The cup labels, clockwise from the current cup.
+#[derive(Debug)]
+pub struct Puzzle {
+    cups: Vec<u32>,
+}
+
+/// Parses the puzzle input.
+pub fn parse(input: &str) -> Result<Puzzle, ParseError> {
+    Ok(Puzzle {
+        cups: parse_cups(input)?,
+    })
+}
+
+/// Labels of the cups after cup 1, once 100 moves are made.
+pub fn part_one(puzzle: &Puzzle) -> String {
+    let next = play(&puzzle.cups, puzzle.cups.len(), 100);
+    let mut labels = String::new();
+    let mut cup = next[1];
+    while cup != 1 {
+        labels.push_str(&cup.to_string());
+        cup = next[cup as usize];
+    }
+    labels
+}
+
+/// Product of the two cups after cup 1, with a million cups and ten million moves.
+pub fn part_two(puzzle: &Puzzle) -> u64 {
+    let next = play(&puzzle.cups, 1_000_000, 10_000_000);
+    let first = next[1];
+    let second = next[first as usize];
+    first as u64 * second as u64
+}
+
+/// Parses the puzzle input and solves both parts.
+pub fn solve(input: &str) -> Result<(String, u64), ParseError> {
+    let puzzle = parse(input)?;
+    Ok((part_one(&puzzle), part_two(&puzzle)))
+}
+
+impl Solution for Puzzle {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        parse(input)
+    }
+
+    fn part_one(&self) -> Answer {
+        part_one(self).into()
+    }
+
+    fn part_two(&self) -> Answer {
+        part_two(self).into()
+    }
+}
+
+/*
+ * 389125467
+ */
+fn parse_cups(input: &str) -> Result<Vec<u32>, ParseError> {
+    let line = input.lines().next().unwrap_or("");
+    if line.is_empty() {
+        return Err(ParseError::new(line, line, "expected cup labels"));
+    }
+    let mut cups = vec![];
+    for (i, ch) in line.char_indices() {
+        let cup = ch.to_digit(10).filter(|&d| d > 0).ok_or_else(|| {
+            ParseError::new(
+                line,
+                &line[i..],
+                format!("expected a cup label, found `{}`", ch),
+            )
+        })?;
+        if cups.contains(&cup) {
+            return Err(ParseError::new(
+                line,
+                &line[i..],
+                format!("duplicate cup {}", cup),
+            ));
+        }
+        cups.push(cup);
+    }
+    // Three cups are picked up, and the destination must be another cup
+    if cups.len() < 5 {
+        return Err(ParseError::new(
+            line,
+            line,
+            format!("expected at least 5 cups, found {}", cups.len()),
+        ));
+    }
+    if let Some(missing) = (1..=cups.len() as u32).find(|c| !cups.contains(c)) {
+        return Err(ParseError::new(
+            line,
+            line,
+            format!(
+                "expected cups labeled 1 to {}, cup {} is missing",
+                cups.len(),
+                missing
+            ),
+        ));
+    }
+    Ok(cups)
+}
+
+/// Plays `moves` moves with `total` cups, at least as many as in `cups`:
+/// `cups` followed by the next labels in increasing order.
+///
+/// The circle is kept as a linked list in a flat array, where `next[cup]` is
+/// the label of the cup clockwise of `cup`; index 0 is unused.
+///
+/// Panics with fewer than 5 cups, which leave no destination cup.
+pub fn play(cups: &[u32], total: usize, moves: usize) -> Vec<u32> {
+    assert!(total >= 5, "expected at least 5 cups");
+    let labels = cups
+        .iter()
+        .copied()
+        .chain(cups.len() as u32 + 1..=total as u32);
+    let mut next = vec![0; total + 1];
+    let mut previous = if total > cups.len() {
+        total as u32
+    } else {
+        cups[cups.len() - 1]
+    };
+    for label in labels {
+        next[previous as usize] = label;
+        previous = label;
+    }
+
+    let max = total as u32;
+    let mut current = cups[0];
+    for _ in 0..moves {
+        let first = next[current as usize];
+        let second = next[first as usize];
+        let third = next[second as usize];
+        next[current as usize] = next[third as usize];
+
+        let mut destination = current;
+        loop {
+            destination = if destination == 1 {
+                max
+            } else {
+                destination - 1
+            };
+            if destination != first && destination != second && destination != third {
+                break;
+            }
+        }
+        next[third as usize] = next[destination as usize];
+        next[destination as usize] = first;
+        current = next[current as usize];
+    }
+    next
+}
+
+#[test]
+fn test_labels_after_one() {
+    let puzzle = parse("389125467").unwrap();
+    assert_eq!("67384529", part_one(&puzzle));
+    let next = play(&puzzle.cups, 9, 10);
+    assert_eq!(vec![0, 9, 6, 7, 1, 8, 5, 4, 3, 2], next);
+}
+
+#[test]
+fn test_million_cups() {
+    let puzzle = parse("389125467").unwrap();
+    assert_eq!(149245887792, part_two(&puzzle));
+}
+
+#[test]
+fn test_parse_cups_errors() {
+    assert_eq!(3, parse_cups("380").unwrap_err().column);
+    assert_eq!(3, parse_cups("121").unwrap_err().column);
+    assert_eq!(
+        "expected cups labeled 1 to 5, cup 2 is missing",
+        parse_cups("13456").unwrap_err().message
+    );
+}
+
+#[test]
+fn test_parse_cups_too_few() {
+    assert_eq!(
+        "expected at least 5 cups, found 2",
+        parse_cups("12").unwrap_err().message
+    );
+    assert!(parse_cups("1").is_err());
+    assert!(parse_cups("21534").is_ok());
+}
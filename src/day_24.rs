@@ -0,0 +1,202 @@
+//! Day 24: Lobby Layout
+
+use std::collections::{HashMap, HashSet};
+use std::ops::Add;
+
+use crate::error::{parse_lines, ParseError};
+use crate::solution::{Answer, Solution};
+
+/// The path to each tile to flip, from the reference tile.
+#[derive(Debug)]
+pub struct Puzzle {
+    paths: Vec<Vec<Direction>>,
+}
+
+/// Parses the puzzle input.
+pub fn parse(input: &str) -> Result<Puzzle, ParseError> {
+    Ok(Puzzle {
+        paths: parse_lines(input, parse_path)?,
+    })
+}
+
+/// Number of tiles left with the black side up.
+pub fn part_one(puzzle: &Puzzle) -> usize {
+    flip_tiles(&puzzle.paths).len()
+}
+
+/// Number of black tiles after 100 days of flipping.
+pub fn part_two(puzzle: &Puzzle) -> usize {
+    let mut black = flip_tiles(&puzzle.paths);
+    for _ in 0..100 {
+        black = next_day(&black);
+    }
+    black.len()
+}
+
+/// Parses the puzzle input and solves both parts.
+pub fn solve(input: &str) -> Result<(usize, usize), ParseError> {
+    let puzzle = parse(input)?;
+    Ok((part_one(&puzzle), part_two(&puzzle)))
+}
+
+impl Solution for Puzzle {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        parse(input)
+    }
+
+    fn part_one(&self) -> Answer {
+        part_one(self).into()
+    }
+
+    fn part_two(&self) -> Answer {
+        part_two(self).into()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Direction {
+    East,
+    SouthEast,
+    SouthWest,
+    West,
+    NorthWest,
+    NorthEast,
+}
+
+impl Direction {
+    const ALL: [Direction; 6] = [
+        Direction::East,
+        Direction::SouthEast,
+        Direction::SouthWest,
+        Direction::West,
+        Direction::NorthWest,
+        Direction::NorthEast,
+    ];
+
+    fn offset(self) -> Hex {
+        let (q, r) = match self {
+            Direction::East => (1, 0),
+            Direction::SouthEast => (0, 1),
+            Direction::SouthWest => (-1, 1),
+            Direction::West => (-1, 0),
+            Direction::NorthWest => (0, -1),
+            Direction::NorthEast => (1, -1),
+        };
+        Hex { q, r }
+    }
+}
+
+/// A tile of the hexagonal floor, in axial coordinates: `q` grows to the east
+/// and `r` to the south-east.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Hex {
+    pub q: i32,
+    pub r: i32,
+}
+
+impl Add for Hex {
+    type Output = Hex;
+
+    fn add(self, other: Hex) -> Hex {
+        Hex {
+            q: self.q + other.q,
+            r: self.r + other.r,
+        }
+    }
+}
+
+impl Hex {
+    fn neighbours(self) -> impl Iterator<Item = Hex> {
+        Direction::ALL.iter().map(move |d| self + d.offset())
+    }
+}
+
+/*
+ * esenee
+ * nwwswee
+ */
+fn parse_path(line: &str) -> Result<Vec<Direction>, ParseError> {
+    let mut path = vec![];
+    let mut rest = line;
+    while !rest.is_empty() {
+        let (direction, length) = match rest.as_bytes() {
+            [b'e', ..] => (Direction::East, 1),
+            [b'w', ..] => (Direction::West, 1),
+            [b's', b'e', ..] => (Direction::SouthEast, 2),
+            [b's', b'w', ..] => (Direction::SouthWest, 2),
+            [b'n', b'w', ..] => (Direction::NorthWest, 2),
+            [b'n', b'e', ..] => (Direction::NorthEast, 2),
+            _ => {
+                return Err(ParseError::new(
+                    line,
+                    rest,
+                    "expected `e`, `se`, `sw`, `w`, `nw` or `ne`",
+                ))
+            }
+        };
+        path.push(direction);
+        rest = &rest[length..];
+    }
+    Ok(path)
+}
+
+// The tiles flipped an odd number of times, which end up black.
+fn flip_tiles(paths: &[Vec<Direction>]) -> HashSet<Hex> {
+    let mut black = HashSet::new();
+    for path in paths {
+        let tile = path
+            .iter()
+            .fold(Hex::default(), |tile, d| tile + d.offset());
+        if !black.remove(&tile) {
+            black.insert(tile);
+        }
+    }
+    black
+}
+
+// Black tiles with zero or more than two black neighbours turn white, and
+// white tiles with exactly two black neighbours turn black.
+fn next_day(black: &HashSet<Hex>) -> HashSet<Hex> {
+    let mut counts: HashMap<Hex, usize> = HashMap::new();
+    for tile in black {
+        for neighbour in tile.neighbours() {
+            *counts.entry(neighbour).or_insert(0) += 1;
+        }
+    }
+    counts
+        .into_iter()
+        .filter(|(tile, count)| *count == 2 || (*count == 1 && black.contains(tile)))
+        .map(|(tile, _)| tile)
+        .collect()
+}
+
+#[cfg(test)]
+const EXAMPLE: &str = "sesenwnenenewseeswwswswwnenewsewsw\nneeenesenwnwwswnenewnwwsewnenwseswesw\nseswneswswsenwwnwse\nnwnwneseeswswnenewneswwnewseswneseene\nswweswneswnenwsewnwneneseenw\neesenwseswswnenwswnwnwsewwnwsene\nsewnenenenesenwsewnenwwwse\nwenwwweseeeweswwwnwwe\nwsweesenenewnwwnwsenewsenwwsesesenwne\nneeswseenwwswnwswswnw\nnenwswwsewswnenenewsenwsenwnesesenew\nenewnwewneswsewnwswenweswnenwsenwsw\nsweneswneswneneenwnewenewwneswswnese\nswwesenesewenwneswnwwneseswwne\nenesenwswwswneneswsenwnewswseenwsese\nwnwnesenesenenwwnenwsewesewsesesew\nnenewswnwewswnenesenwnesewesw\neneswnwswnwsenenwnwnwwseeswneewsenese\nneswnwewnwnwseenwseesewsenwsweewe\nwseweeenwnesenwwwswnew";
+
+#[test]
+fn test_flip_tiles() {
+    let puzzle = parse(EXAMPLE).unwrap();
+    assert_eq!(10, part_one(&puzzle));
+    let tile = parse_path("nwwswee").unwrap();
+    assert_eq!(
+        Hex::default(),
+        flip_tiles(&[tile]).into_iter().next().unwrap()
+    );
+}
+
+#[test]
+fn test_next_day() {
+    let puzzle = parse(EXAMPLE).unwrap();
+    let mut black = flip_tiles(&puzzle.paths);
+    black = next_day(&black);
+    assert_eq!(15, black.len());
+    black = next_day(&black);
+    assert_eq!(12, black.len());
+    assert_eq!(2208, part_two(&puzzle));
+}
+
+#[test]
+fn test_parse_path_error() {
+    let err = parse_path("esenxe").unwrap_err();
+    assert_eq!(4, err.column);
+}
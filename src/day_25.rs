@@ -0,0 +1,90 @@
+//! Day 25: Combo Breaker
+
+use crate::error::{parse_field, ParseError};
+use crate::modular::{discrete_log, mod_pow};
+use crate::solution::{Answer, Solution};
+
+const MODULUS: i64 = 20201227;
+const SUBJECT: i64 = 7;
+
+/// The public keys of the card and of the door.
+#[derive(Debug)]
+pub struct Puzzle {
+    card_key: i64,
+    door_key: i64,
+}
+
+/// Parses the puzzle input.
+pub fn parse(input: &str) -> Result<Puzzle, ParseError> {
+    let (card_key, door_key) = parse_keys(input)?;
+    Ok(Puzzle { card_key, door_key })
+}
+
+/// The encryption key shared by the card and the door.
+pub fn part_one(puzzle: &Puzzle) -> Option<i64> {
+    let loop_size = discrete_log(SUBJECT, puzzle.card_key, MODULUS)?;
+    Some(mod_pow(puzzle.door_key, loop_size, MODULUS))
+}
+
+/// There is no puzzle for the last star.
+pub fn part_two(_puzzle: &Puzzle) -> &'static str {
+    "Merry Christmas!"
+}
+
+/// Parses the puzzle input and solves both parts.
+pub fn solve(input: &str) -> Result<(Option<i64>, &'static str), ParseError> {
+    let puzzle = parse(input)?;
+    Ok((part_one(&puzzle), part_two(&puzzle)))
+}
+
+impl Solution for Puzzle {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        parse(input)
+    }
+
+    fn part_one(&self) -> Answer {
+        part_one(self).into()
+    }
+
+    fn part_two(&self) -> Answer {
+        part_two(self).into()
+    }
+}
+
+/*
+ * 5764801
+ * 17807724
+ */
+fn parse_keys(input: &str) -> Result<(i64, i64), ParseError> {
+    let mut keys = [0; 2];
+    let mut lines = input.lines();
+    for (i, key) in keys.iter_mut().enumerate() {
+        let line = match lines.next() {
+            Some(line) => line,
+            None => return Err(ParseError::new("", "", "expected a public key").offset(i)),
+        };
+        *key = parse_field(line, line, "a public key").map_err(|e| e.offset(i))?;
+        if !(1..MODULUS).contains(key) {
+            return Err(ParseError::new(
+                line,
+                line,
+                format!("expected a public key below {}", MODULUS),
+            )
+            .offset(i));
+        }
+    }
+    Ok((keys[0], keys[1]))
+}
+
+#[test]
+fn test_encryption_key() {
+    let puzzle = parse("5764801\n17807724").unwrap();
+    assert_eq!(Some(14897079), part_one(&puzzle));
+}
+
+#[test]
+fn test_parse_keys_errors() {
+    assert_eq!(2, parse("5764801").unwrap_err().line);
+    let err = parse("5764801\n20201227").unwrap_err();
+    assert_eq!("expected a public key below 20201227", err.message);
+}
@@ -0,0 +1,112 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// Malformed puzzle input, pointing at the offending line and column.
+///
+/// Parsers report lines relative to the text they were given; callers that
+/// parse a larger input shift them with `offset`, and `main` attaches the file.
+#[derive(Debug, PartialEq)]
+pub struct ParseError {
+    pub file: Option<String>,
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub message: String,
+}
+
+impl ParseError {
+    // `part` must be a slice of `line`: the error points at where it starts.
+    pub fn new(line: &str, part: &str, message: impl Into<String>) -> ParseError {
+        ParseError {
+            file: None,
+            line: 1,
+            column: column_of(line, part),
+            text: line.to_string(),
+            message: message.into(),
+        }
+    }
+
+    pub fn offset(mut self, lines: usize) -> ParseError {
+        self.line += lines;
+        self
+    }
+
+    pub fn in_file(mut self, file: &str) -> ParseError {
+        self.file = Some(file.to_string());
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{}:", file)?;
+        }
+        writeln!(f, "{}:{}: {}", self.line, self.column, self.message)?;
+        writeln!(f, "    {}", self.text)?;
+        write!(f, "    {:>width$}", "^", width = self.column)
+    }
+}
+
+impl Error for ParseError {}
+
+// 1-based column, in chars, at which `part` starts inside `line`.
+fn column_of(line: &str, part: &str) -> usize {
+    let offset = (part.as_ptr() as usize).wrapping_sub(line.as_ptr() as usize);
+    if offset <= line.len() && line.is_char_boundary(offset) {
+        line[..offset].chars().count() + 1
+    } else {
+        1
+    }
+}
+
+// Parses `field`, a slice of `line`, into a number or any other `FromStr` value.
+pub fn parse_field<T: FromStr>(line: &str, field: &str, expected: &str) -> Result<T, ParseError> {
+    field.parse::<T>().map_err(|_| {
+        ParseError::new(
+            line,
+            field,
+            format!("expected {}, found `{}`", expected, field),
+        )
+    })
+}
+
+// Parses every line of `input` with `parse`, reporting errors at the right line.
+pub fn parse_lines<T, F>(input: &str, parse: F) -> Result<Vec<T>, ParseError>
+where
+    F: Fn(&str) -> Result<T, ParseError>,
+{
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| parse(line).map_err(|e| e.offset(i)))
+        .collect()
+}
+
+#[test]
+fn test_parse_field_column() {
+    let line = "1-x a: abcde";
+    let err = parse_field::<usize>(line, &line[2..3], "a number").unwrap_err();
+    assert_eq!(1, err.line);
+    assert_eq!(3, err.column);
+    assert_eq!("expected a number, found `x`", err.message);
+}
+
+#[test]
+fn test_parse_lines_offset() {
+    let err = parse_lines("1\n2\nthree", |l| parse_field::<i32>(l, l, "a number")).unwrap_err();
+    assert_eq!(3, err.line);
+    assert_eq!(1, err.column);
+    assert_eq!("three", err.text);
+}
+
+#[test]
+fn test_display() {
+    let line = "mem[8] = x";
+    let err = ParseError::new(line, &line[9..], "expected a number").in_file("data/14/input.txt");
+    assert_eq!(
+        "data/14/input.txt:1:10: expected a number\n    mem[8] = x\n             ^",
+        err.to_string()
+    );
+}
@@ -0,0 +1,202 @@
+//! Rectangular grids of cells, as used by the days working on maps and images.
+
+use crate::error::ParseError;
+
+/// A rectangular grid of cells, stored row by row.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    /// Builds a grid with the cell at `(x, y)` given by `cell(x, y)`.
+    pub fn from_fn<F>(width: usize, height: usize, cell: F) -> Grid<T>
+    where
+        F: Fn(usize, usize) -> T,
+    {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| cell(x, y))
+            .collect();
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Parses one row per line of `input`, converting each char with `cell`.
+    /// `expected` describes the valid chars in error messages.
+    pub fn parse<F>(input: &str, cell: F, expected: &str) -> Result<Grid<T>, ParseError>
+    where
+        F: Fn(char) -> Option<T>,
+    {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = vec![];
+        for (y, line) in input.lines().enumerate() {
+            let row = parse_row(line, &cell, expected).map_err(|e| e.offset(y))?;
+            if *width.get_or_insert(row.len()) != row.len() {
+                return Err(ParseError::new(
+                    line,
+                    line,
+                    format!("expected a row of {} cells", width.unwrap_or(0)),
+                )
+                .offset(y));
+            }
+            cells.extend(row);
+            height += 1;
+        }
+        Ok(Grid {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> &T {
+        assert!(
+            x < self.width,
+            "x {} outside a grid of width {}",
+            x,
+            self.width
+        );
+        &self.cells[y * self.width + x]
+    }
+
+    /// The cell at `(x, y)`, or `None` outside the grid.
+    pub fn get_checked(&self, x: isize, y: isize) -> Option<&T> {
+        if x < 0 || y < 0 || x as usize >= self.width || y as usize >= self.height {
+            return None;
+        }
+        Some(self.get(x as usize, y as usize))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn column(&self, x: usize) -> Vec<T> {
+        (0..self.height).map(|y| self.get(x, y).clone()).collect()
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn cells(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    /// The grid turned a quarter turn clockwise.
+    pub fn rotate(&self) -> Grid<T> {
+        Grid::from_fn(self.height, self.width, |x, y| {
+            self.get(y, self.height - 1 - x).clone()
+        })
+    }
+
+    /// The grid mirrored left to right.
+    pub fn flip(&self) -> Grid<T> {
+        Grid::from_fn(self.width, self.height, |x, y| {
+            self.get(self.width - 1 - x, y).clone()
+        })
+    }
+
+    /// The eight ways to orient the grid: each of the four rotations, as is
+    /// and flipped.
+    pub fn orientations(&self) -> Vec<Grid<T>> {
+        let mut orientations = vec![];
+        let mut grid = self.clone();
+        for _ in 0..4 {
+            let rotated = grid.rotate();
+            orientations.push(grid.flip());
+            orientations.push(grid);
+            grid = rotated;
+        }
+        orientations
+    }
+
+    /// The grid without its `margin` outermost cells on every side.
+    pub fn crop(&self, margin: usize) -> Grid<T> {
+        let width = self.width.saturating_sub(2 * margin);
+        let height = self.height.saturating_sub(2 * margin);
+        Grid::from_fn(width, height, |x, y| {
+            self.get(x + margin, y + margin).clone()
+        })
+    }
+}
+
+/// Parses a row of cells, converting each char with `cell`. `expected`
+/// describes the valid chars in error messages.
+pub fn parse_row<T, F>(row: &str, cell: F, expected: &str) -> Result<Vec<T>, ParseError>
+where
+    F: Fn(char) -> Option<T>,
+{
+    row.char_indices()
+        .map(|(i, c)| {
+            cell(c).ok_or_else(|| {
+                ParseError::new(
+                    row,
+                    &row[i..],
+                    format!("expected {}, found `{}`", expected, c),
+                )
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+fn parse_digits(input: &str) -> Grid<u32> {
+    Grid::parse(input, |c| c.to_digit(10), "a digit").unwrap()
+}
+
+#[test]
+fn test_parse() {
+    let grid = parse_digits("123\n456");
+    assert_eq!((3, 2), (grid.width(), grid.height()));
+    assert_eq!(6, *grid.get(2, 1));
+    assert_eq!(Some(&6), grid.get_checked(2, 1));
+    assert_eq!(None, grid.get_checked(3, 1));
+    assert_eq!(None, grid.get_checked(0, -1));
+    assert_eq!(&[4, 5, 6], grid.row(1));
+    assert_eq!(vec![2, 5], grid.column(1));
+
+    let err = Grid::parse("123\n4x6", |c| c.to_digit(10), "a digit").unwrap_err();
+    assert_eq!((2, 2), (err.line, err.column));
+    assert_eq!("expected a digit, found `x`", err.message);
+    let err = Grid::parse("123\n45", |c| c.to_digit(10), "a digit").unwrap_err();
+    assert_eq!(2, err.line);
+}
+
+#[test]
+fn test_rotate_and_flip() {
+    let grid = parse_digits("123\n456");
+    assert_eq!(parse_digits("41\n52\n63"), grid.rotate());
+    assert_eq!(parse_digits("321\n654"), grid.flip());
+    assert_eq!(grid, grid.rotate().rotate().rotate().rotate());
+}
+
+#[test]
+fn test_orientations() {
+    let orientations = parse_digits("12\n34").orientations();
+    assert_eq!(8, orientations.len());
+    for (i, grid) in orientations.iter().enumerate() {
+        assert!(!orientations[i + 1..].contains(grid));
+    }
+}
+
+#[test]
+fn test_crop() {
+    let grid = parse_digits("1234\n5678\n9012\n3456");
+    assert_eq!(parse_digits("67\n01"), grid.crop(1));
+}
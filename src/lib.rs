@@ -0,0 +1,46 @@
+//! Solutions to [Advent of Code 2020](https://adventofcode.com/2020).
+//!
+//! Every `day_NN` module exposes the same entry points:
+//!
+//! - `parse(input: &str) -> Result<Puzzle, ParseError>`
+//! - `part_one(&Puzzle)` and `part_two(&Puzzle)`, returning the typed answers
+//! - `solve(input: &str)`, parsing the input and solving both parts
+//!
+//! ```
+//! let (first, second) = aoc2020::day_06::solve("abc\n\na\nb\nc").unwrap();
+//! assert_eq!((6, 3), (first, second));
+//! ```
+//!
+//! Each `Puzzle` also implements [`solution::Solution`], which is how the
+//! [`registry`] runs any day from the command line.
+
+pub mod day_01;
+pub mod day_02;
+pub mod day_03;
+pub mod day_04;
+pub mod day_05;
+pub mod day_06;
+pub mod day_07;
+pub mod day_08;
+pub mod day_09;
+pub mod day_10;
+pub mod day_11;
+pub mod day_12;
+pub mod day_13;
+pub mod day_14;
+pub mod day_15;
+pub mod day_16;
+pub mod day_17;
+pub mod day_18;
+pub mod day_19;
+pub mod day_20;
+pub mod day_21;
+pub mod day_22;
+pub mod day_23;
+pub mod day_24;
+pub mod day_25;
+pub mod error;
+pub mod grid;
+pub mod modular;
+pub mod registry;
+pub mod solution;
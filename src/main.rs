@@ -1,84 +1,367 @@
+use std::fs;
+use std::io::{self, prelude::*};
+use std::path::Path;
+use std::process;
 use structopt::StructOpt;
 
-mod day_01;
-mod day_02;
-mod day_03;
-mod day_04;
-mod day_05;
-mod day_06;
-mod day_07;
-mod day_08;
-mod day_09;
-mod day_10;
-mod day_11;
-mod day_12;
-mod day_13;
-mod day_14;
+mod answers;
+mod audit;
+mod runner;
+mod scaffold;
+
+use aoc2020::error::ParseError;
+use aoc2020::registry;
+use aoc2020::solution::Parts;
+use aoc2020::{day_01, day_02};
+use audit::ReportFormat;
+use runner::Format;
 
 #[derive(Debug, StructOpt)]
 struct Cli {
-    day: String,
+    /// Days to run, e.g. `01 14`, `03..=09` or `all`
+    days: Vec<String>,
+    /// Puzzle input file, or `-` to read from stdin [default: data/NN/input.txt]
+    #[structopt(short = "i", long = "input", raw(global = "true"))]
+    input: Option<String>,
+    /// Part to run: 1, 2 or both
+    #[structopt(
+        short = "p",
+        long = "part",
+        default_value = "both",
+        raw(global = "true")
+    )]
+    part: Parts,
+    /// Output format: text or json (one record per line)
+    #[structopt(long = "format", default_value = "text")]
+    format: Format,
+    /// Compare the answers with the ones recorded in answers.txt next to the input
+    #[structopt(long = "check")]
+    check: bool,
+    /// Report how long parsing and each part took
+    #[structopt(long = "time")]
+    time: bool,
+    /// Print how the answers are found, for the days that support it
+    #[structopt(short = "v", long = "verbose")]
+    verbose: bool,
+    /// Day 01: number of entries that must sum to the target [default: 2 for part 1, 3 for part 2]
+    #[structopt(long = "k")]
+    k: Option<usize>,
+    /// Day 01: sum the entries must add up to [default: 2020]
+    #[structopt(long = "target")]
+    target: Option<i64>,
+    /// Day 01: list every distinct set of entries summing to the target, flagging ambiguous inputs
+    #[structopt(long = "all-sums")]
+    all_sums: bool,
+    /// Day 02: count the passwords valid under these policies instead: sled, toboggan,
+    /// exact-count=N, forbid=C, min-length=N or require=CHARS
+    #[structopt(long = "policy", raw(number_of_values = "1"))]
+    policies: Vec<String>,
+    /// Day 02: list every password rejected by a policy and why, as text or csv
+    #[structopt(long = "report")]
+    report: Option<ReportFormat>,
+    /// List the implemented days
+    #[structopt(long = "list")]
+    list: bool,
+    #[structopt(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Debug, StructOpt)]
+enum Command {
+    /// Run each part repeatedly and report min/median/mean durations
+    #[structopt(name = "bench")]
+    Bench {
+        /// Days to benchmark, e.g. `09`, `03..=09` or `all`
+        days: Vec<String>,
+        /// Number of runs of each part
+        #[structopt(short = "n", long = "runs", default_value = "10")]
+        runs: usize,
+    },
+    /// Create src/day_NN.rs from a template, data/NN/, and register the day
+    #[structopt(name = "new")]
+    New {
+        /// Day to create, from 1 to 25
+        day: u8,
+        /// Puzzle title, used in the module documentation
+        #[structopt(default_value = "TODO")]
+        title: String,
+    },
+}
+
+fn read_input(path: &str) -> io::Result<String> {
+    if path == "-" {
+        let mut input = String::new();
+        io::stdin().read_to_string(&mut input)?;
+        Ok(input)
+    } else {
+        fs::read_to_string(path)
+    }
 }
 
 fn main() {
     let args = Cli::from_args();
-    match args.day.as_str() {
-        "01" => {
-            day_01::first_solution();
-            day_01::second_solution();
-        }
-        "02" => {
-            day_02::first_solution();
-            day_02::second_solution();
+    if args.list {
+        for day in registry::DAYS {
+            println!("{:02}", day.number);
         }
-        "03" => {
-            day_03::first_solution();
-            day_03::second_solution();
+        return;
+    }
+    if let Some(Command::New { day, title }) = &args.command {
+        new_day(*day, title);
+        return;
+    }
+    let selectors = match &args.command {
+        Some(Command::Bench { days, .. }) => days,
+        _ => &args.days,
+    };
+    let days = select_days(selectors);
+    if args.input.is_some() && days.len() > 1 {
+        eprintln!("--input can only be used with a single day");
+        process::exit(1);
+    }
+
+    if args.format == Format::Json && (args.check || args.command.is_some()) {
+        eprintln!("--format json cannot be combined with --check or bench");
+        process::exit(1);
+    }
+    if args.verbose && (args.format == Format::Json || args.command.is_some()) {
+        eprintln!("--verbose cannot be combined with --format json or bench");
+        process::exit(1);
+    }
+    if args.k.is_some() || args.target.is_some() || args.all_sums {
+        if args.check || args.format == Format::Json || args.command.is_some() {
+            eprintln!("--k, --target and --all-sums cannot be combined with --check, --format json or bench");
+            process::exit(1);
         }
-        "04" => {
-            day_04::first_solution();
-            day_04::second_solution();
+        match days.as_slice() {
+            [day] if day.number == 1 => run_k_sum(&args, day),
+            _ => {
+                eprintln!("--k, --target and --all-sums can only be used with day 01");
+                process::exit(1);
+            }
         }
-        "05" => {
-            day_05::first_solution();
-            day_05::second_solution();
+        return;
+    }
+    if !args.policies.is_empty() || args.report.is_some() {
+        if args.check || args.format == Format::Json || args.command.is_some() {
+            eprintln!(
+                "--policy and --report cannot be combined with --check, --format json or bench"
+            );
+            process::exit(1);
         }
-        "06" => {
-            day_06::first_solution();
-            day_06::second_solution();
+        match days.as_slice() {
+            [day] if day.number == 2 => run_policies(&args, day),
+            _ => {
+                eprintln!("--policy and --report can only be used with day 02");
+                process::exit(1);
+            }
         }
-        "07" => {
-            day_07::first_solution();
-            day_07::second_solution();
+        return;
+    }
+    if args.check && args.input.as_deref() == Some("-") {
+        eprintln!("--check needs an input file to find the recorded answers");
+        process::exit(1);
+    }
+
+    let parts = args.part.to_vec();
+    let repeat = match args.command {
+        Some(Command::Bench { runs, .. }) => runs.max(1),
+        _ => 1,
+    };
+    let mut runs = vec![];
+    let mut expected = vec![];
+    let several = days.len() > 1;
+    for day in days {
+        let path = args.input.clone().unwrap_or_else(|| day.input_path());
+        // Running several days skips the ones whose input has not been added
+        // yet, but not a missing input with recorded answers, which --check
+        // must report
+        let missing = !Path::new(&path).exists();
+        if several && missing && !answers::answers_path(&path).exists() {
+            eprintln!("Day {:02}: no input in {}, skipped", day.number, path);
+            continue;
         }
-        "08" => {
-            day_08::first_solution();
-            day_08::second_solution();
+        let input = match read_input(&path) {
+            Ok(input) => input,
+            Err(err) => {
+                eprintln!("Unable to read {}: {}", path, err);
+                process::exit(1);
+            }
+        };
+        match runner::run_day(day, &input, &parts, repeat, args.verbose) {
+            Ok(run) => runs.push(run),
+            Err(err) => {
+                eprintln!("Invalid input for day {:02}", day.number);
+                let file = if path == "-" { "<stdin>" } else { &path };
+                eprintln!("{}", err.in_file(file));
+                process::exit(1);
+            }
         }
-        "09" => {
-            day_09::first_solution();
-            day_09::second_solution();
+        if args.check {
+            expected.push(answers::read_answers(&path).unwrap_or_else(|err| {
+                eprintln!("Unable to read answers for day {:02}: {}", day.number, err);
+                vec![]
+            }));
         }
-        "10" => {
-            day_10::first_solution();
-            day_10::second_solution();
+    }
+
+    match args.command {
+        Some(Command::Bench { .. }) => runner::print_bench(&runs),
+        _ if args.check => {
+            if !answers::print_checks(&runs, &expected) {
+                process::exit(1);
+            }
         }
-        "11" => {
-            day_11::first_solution();
-            day_11::second_solution();
+        _ if args.format == Format::Json => runner::print_json(&runs),
+        _ if runs.len() > 1 => runner::print_table(&runs, args.time),
+        _ => runner::print_lines(&runs, args.time),
+    }
+}
+
+// Finds the day 01 entries that sum to `--target`, for `--k` or each part,
+// listing all of them with `--all-sums`.
+fn run_k_sum(args: &Cli, day: &registry::Day) {
+    let puzzle = parse_day(args, day, day_01::parse);
+
+    let target = args.target.unwrap_or(2020);
+    let ks = match args.k {
+        Some(k) => vec![k],
+        None => args
+            .part
+            .to_vec()
+            .iter()
+            .map(|p| p.number() as usize + 1)
+            .collect(),
+    };
+    for k in ks {
+        let prefix = format!("Day 01 k={} target={}", k, target);
+        if args.all_sums {
+            let all = day_01::find_all_k_sums(puzzle.numbers(), k, target);
+            match all.len() {
+                0 => println!("{}: no solution", prefix),
+                1 => println!("{}: 1 solution", prefix),
+                n => println!("{}: {} solutions, the input is ambiguous", prefix, n),
+            }
+            for entries in all {
+                println!("    {}", format_entries(&entries));
+            }
+            continue;
         }
-        "12" => {
-            day_12::first_solution();
-            day_12::second_solution();
+        match day_01::find_k_sum(puzzle.numbers(), k, target) {
+            Some(entries) => println!("{}: {}", prefix, format_entries(&entries)),
+            None => println!("{}: no solution", prefix),
         }
-        "13" => {
-            day_13::first_solution();
-            day_13::second_solution();
+    }
+}
+
+// Counts the day 02 passwords valid under every `--policy`, or the policy of
+// each part, listing the rejected ones with `--report`.
+fn run_policies(args: &Cli, day: &registry::Day) {
+    let puzzle = parse_day(args, day, day_02::parse);
+    let specs = if args.policies.is_empty() {
+        args.part
+            .to_vec()
+            .iter()
+            .map(|p| match p.number() {
+                1 => "sled".to_string(),
+                _ => "toboggan".to_string(),
+            })
+            .collect()
+    } else {
+        args.policies.clone()
+    };
+    let policies: Vec<_> = specs
+        .iter()
+        .map(|spec| {
+            day_02::parse_policy(spec).unwrap_or_else(|err| {
+                eprintln!("{}", err);
+                process::exit(1);
+            })
+        })
+        .collect();
+    let valid = day_02::count_valid(&puzzle, &policies);
+    let total = puzzle.rules().len();
+    match &args.report {
+        Some(format) => {
+            let rejections = day_02::audit(&puzzle, &policies);
+            audit::print_report(&rejections, valid, total, format);
         }
-        "14" => {
-            day_14::first_solution();
-            day_14::second_solution();
+        None => println!(
+            "Day 02 policy {}: {} of {} passwords valid",
+            specs.join(", "),
+            valid,
+            total
+        ),
+    }
+}
+
+// Reads and parses the input of a single day, for the options that call into
+// its module directly.
+fn parse_day<T>(args: &Cli, day: &registry::Day, parse: fn(&str) -> Result<T, ParseError>) -> T {
+    let path = args.input.clone().unwrap_or_else(|| day.input_path());
+    let input = read_input(&path).unwrap_or_else(|err| {
+        eprintln!("Unable to read {}: {}", path, err);
+        process::exit(1);
+    });
+    parse(&input).unwrap_or_else(|err| {
+        eprintln!("Invalid input for day {:02}", day.number);
+        let file = if path == "-" { "<stdin>" } else { &path };
+        eprintln!("{}", err.in_file(file));
+        process::exit(1);
+    })
+}
+
+fn format_entries(entries: &[i64]) -> String {
+    let sum: Vec<String> = entries.iter().map(|e| e.to_string()).collect();
+    match day_01::product(entries) {
+        Some(product) => format!("{} (product {})", sum.join(" + "), product),
+        None => format!("{} (product overflows)", sum.join(" + ")),
+    }
+}
+
+fn new_day(day: u8, title: &str) {
+    if !(1..=25).contains(&day) {
+        eprintln!("Day must be between 1 and 25");
+        process::exit(1);
+    }
+    if registry::find(day).is_some() {
+        eprintln!("Day {:02} is already registered", day);
+        process::exit(1);
+    }
+    if let Err(err) = scaffold::new_day(day, title) {
+        eprintln!("Unable to create day {:02}: {}", day, err);
+        process::exit(1);
+    }
+    println!("Created src/day_{:02}.rs and data/{:02}/", day, day);
+    println!("Add the puzzle input to data/{:02}/input.txt", day);
+}
+
+// Days selected by all of `selectors`, each once and in the order given.
+// Exits when a selector is invalid or nothing is selected.
+fn select_days(selectors: &[String]) -> Vec<&'static registry::Day> {
+    let mut days: Vec<&registry::Day> = vec![];
+    let mut valid = true;
+    for selector in selectors {
+        match registry::select(selector) {
+            Ok(selected) => {
+                for day in selected {
+                    if !days.iter().any(|d| d.number == day.number) {
+                        days.push(day);
+                    }
+                }
+            }
+            Err(err) => {
+                eprintln!("{}", err);
+                valid = false;
+            }
         }
-        _ => println!("Not yet implemented"),
-    };
+    }
+    if !valid {
+        process::exit(1);
+    }
+    if days.is_empty() {
+        eprintln!("No day selected, give days like `01 14`, `03..=09` or `all`");
+        process::exit(1);
+    }
+    days
 }
@@ -0,0 +1,110 @@
+//! Modular arithmetic, shared by the days working with remainders.
+
+use std::collections::HashMap;
+
+/// Extended Euclidean algorithm: `(g, x, y)` such that `a * x + b * y = g = gcd(a, b)`.
+///
+/// From <https://rosettacode.org/wiki/Chinese_remainder_theorem#Rust>
+#[allow(clippy::many_single_char_names)]
+pub fn egcd(a: i64, b: i64) -> (i64, i64, i64) {
+    if a == 0 {
+        (b, 0, 1)
+    } else {
+        let (g, x, y) = egcd(b % a, a);
+        (g, y - (b / a) * x, x)
+    }
+}
+
+/// Modular inverse of `x` modulo `n`, if they are coprime.
+///
+/// From <https://rosettacode.org/wiki/Chinese_remainder_theorem#Rust>
+pub fn mod_inv(x: i64, n: i64) -> Option<i64> {
+    let (g, x, _) = egcd(x, n);
+    if g == 1 {
+        Some((x % n + n) % n)
+    } else {
+        None
+    }
+}
+
+/// `x`, modulo the product of `modulii`, such that `x ≡ residues[i] (mod modulii[i])`
+/// for every `i`, if the moduli are pairwise coprime.
+///
+/// From <https://rosettacode.org/wiki/Chinese_remainder_theorem#Rust>
+pub fn chinese_remainder(residues: &[i64], modulii: &[i64]) -> Option<i64> {
+    let prod = modulii.iter().product::<i64>();
+    let mut sum = 0;
+    for (&residue, &modulus) in residues.iter().zip(modulii) {
+        let p = prod / modulus;
+        sum += residue * mod_inv(p, modulus)? * p
+    }
+
+    Some(sum % prod)
+}
+
+/// `base` to the power `exponent`, modulo `modulus`, by repeated squaring.
+pub fn mod_pow(base: i64, exponent: i64, modulus: i64) -> i64 {
+    let mut result = 1 % modulus;
+    let mut base = base.rem_euclid(modulus);
+    let mut exponent = exponent;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = mod_mul(result, base, modulus);
+        }
+        base = mod_mul(base, base, modulus);
+        exponent >>= 1;
+    }
+    result
+}
+
+fn mod_mul(a: i64, b: i64, modulus: i64) -> i64 {
+    (a as i128 * b as i128 % modulus as i128) as i64
+}
+
+/// Smallest `x` such that `base^x ≡ target (mod modulus)`, if any, found with
+/// the baby-step giant-step algorithm in `O(sqrt(modulus))` steps. `base` must
+/// be coprime with `modulus`.
+pub fn discrete_log(base: i64, target: i64, modulus: i64) -> Option<i64> {
+    let steps = (modulus as f64).sqrt().ceil() as i64;
+    let target = target.rem_euclid(modulus);
+
+    // Baby steps: base^j for every j < steps, keeping the smallest j.
+    let mut baby_steps = HashMap::new();
+    let mut power = 1 % modulus;
+    for j in 0..steps {
+        baby_steps.entry(power).or_insert(j);
+        power = mod_mul(power, base, modulus);
+    }
+
+    // Giant steps: target * base^(-i * steps), until it is a baby step.
+    let factor = mod_inv(mod_pow(base, steps, modulus), modulus)?;
+    let mut gamma = target;
+    for i in 0..steps {
+        if let Some(j) = baby_steps.get(&gamma) {
+            return Some(i * steps + j);
+        }
+        gamma = mod_mul(gamma, factor, modulus);
+    }
+    None
+}
+
+#[test]
+fn test_mod_inv() {
+    assert_eq!(Some(4), mod_inv(3, 11));
+    assert_eq!(None, mod_inv(2, 4));
+}
+
+#[test]
+fn test_mod_pow() {
+    assert_eq!(5764801, mod_pow(7, 8, 20201227));
+    assert_eq!(14897079, mod_pow(17807724, 8, 20201227));
+    assert_eq!(1, mod_pow(3, 0, 7));
+}
+
+#[test]
+fn test_discrete_log() {
+    assert_eq!(Some(8), discrete_log(7, 5764801, 20201227));
+    assert_eq!(Some(11), discrete_log(7, 17807724, 20201227));
+    assert_eq!(Some(0), discrete_log(3, 1, 7));
+    assert_eq!(None, discrete_log(2, 3, 7));
+}
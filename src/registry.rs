@@ -0,0 +1,111 @@
+use crate::error::ParseError;
+use crate::solution::Solution;
+
+pub struct Day {
+    pub number: u8,
+    pub parse: fn(&str) -> Result<Box<dyn Solution>, ParseError>,
+}
+
+fn parse<S: Solution + 'static>(input: &str) -> Result<Box<dyn Solution>, ParseError> {
+    Ok(Box::new(S::parse(input)?))
+}
+
+macro_rules! day {
+    ($number:expr, $module:ident) => {
+        Day {
+            number: $number,
+            parse: parse::<crate::$module::Puzzle>,
+        }
+    };
+}
+
+// Every implemented day, in calendar order. `aoc2020 new <day>` adds new entries here.
+pub const DAYS: &[Day] = &[
+    day!(1, day_01),
+    day!(2, day_02),
+    day!(3, day_03),
+    day!(4, day_04),
+    day!(5, day_05),
+    day!(6, day_06),
+    day!(7, day_07),
+    day!(8, day_08),
+    day!(9, day_09),
+    day!(10, day_10),
+    day!(11, day_11),
+    day!(12, day_12),
+    day!(13, day_13),
+    day!(14, day_14),
+    day!(15, day_15),
+    day!(16, day_16),
+    day!(17, day_17),
+    day!(18, day_18),
+    day!(19, day_19),
+    day!(20, day_20),
+    day!(21, day_21),
+    day!(22, day_22),
+    day!(23, day_23),
+    day!(24, day_24),
+    day!(25, day_25),
+];
+
+impl Day {
+    // Default location of the puzzle input, relative to the repository root.
+    pub fn input_path(&self) -> String {
+        format!("data/{:02}/input.txt", self.number)
+    }
+}
+
+pub fn find(number: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.number == number)
+}
+
+/*
+ * Day selectors accepted on the command line:
+ * all, 07, 03..=09, 03..10
+ * Days inside a range that are not implemented yet are skipped.
+ */
+pub fn select(selector: &str) -> Result<Vec<&'static Day>, String> {
+    if selector == "all" {
+        return Ok(DAYS.iter().collect());
+    }
+    let parse_day = |s: &str| {
+        s.parse::<u8>()
+            .map_err(|_| format!("invalid day `{}`", selector))
+    };
+    let range = if let Some((from, to)) = selector.split_once("..=") {
+        parse_day(from)?..=parse_day(to)?
+    } else if let Some((from, to)) = selector.split_once("..") {
+        parse_day(from)?..=parse_day(to)?.saturating_sub(1)
+    } else {
+        let number = parse_day(selector)?;
+        return match find(number) {
+            Some(day) => Ok(vec![day]),
+            None => Err(format!("Day {:02}: not yet implemented", number)),
+        };
+    };
+    Ok(DAYS.iter().filter(|d| range.contains(&d.number)).collect())
+}
+
+#[test]
+fn test_days_are_unique_and_ordered() {
+    for pair in DAYS.windows(2) {
+        assert!(pair[0].number < pair[1].number);
+    }
+}
+
+#[test]
+fn test_select_days() {
+    let numbers = |selector| {
+        select(selector)
+            .unwrap()
+            .iter()
+            .map(|d| d.number)
+            .collect::<Vec<u8>>()
+    };
+    assert_eq!(vec![7], numbers("07"));
+    assert_eq!(vec![3, 4, 5], numbers("03..=05"));
+    assert_eq!(vec![3, 4], numbers("3..5"));
+    assert_eq!(DAYS.len(), numbers("all").len());
+    assert!(select("26").is_err());
+    assert!(select("x..=3").is_err());
+}
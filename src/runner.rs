@@ -0,0 +1,271 @@
+use serde::Serialize;
+use std::io;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use aoc2020::error::ParseError;
+use aoc2020::registry::Day;
+use aoc2020::solution::{Answer, Part};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("invalid format `{}`, expected text or json", s)),
+        }
+    }
+}
+
+pub struct PartRun {
+    pub part: Part,
+    pub answer: Answer,
+    pub durations: Vec<Duration>,
+}
+
+pub struct DayRun {
+    pub day: u8,
+    pub parse: Vec<Duration>,
+    pub parts: Vec<PartRun>,
+}
+
+// Parses the input and solves each part `repeat` times, keeping every duration.
+// With `verbose`, days that support it print a trace while solving.
+pub fn run_day(
+    day: &Day,
+    input: &str,
+    parts: &[Part],
+    repeat: usize,
+    verbose: bool,
+) -> Result<DayRun, ParseError> {
+    let mut parse = vec![];
+    let mut solution = None;
+    for _ in 0..repeat {
+        let start = Instant::now();
+        solution = Some((day.parse)(input)?);
+        parse.push(start.elapsed());
+    }
+    let solution = solution.expect("expected at least one run");
+
+    let parts = parts
+        .iter()
+        .map(|&part| {
+            let mut durations = vec![];
+            let mut answer = None;
+            for _ in 0..repeat {
+                let start = Instant::now();
+                answer = Some(if verbose {
+                    solution.solve_verbose(part, &mut io::stdout().lock())
+                } else {
+                    solution.solve(part)
+                });
+                durations.push(start.elapsed());
+            }
+            PartRun {
+                part,
+                answer: answer.expect("expected at least one run"),
+                durations,
+            }
+        })
+        .collect();
+
+    Ok(DayRun {
+        day: day.number,
+        parse,
+        parts,
+    })
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+}
+
+impl Stats {
+    pub fn from_durations(durations: &[Duration]) -> Stats {
+        let mut sorted = durations.to_vec();
+        sorted.sort();
+        let middle = sorted.len() / 2;
+        let median = if sorted.len().is_multiple_of(2) {
+            (sorted[middle - 1] + sorted[middle]) / 2
+        } else {
+            sorted[middle]
+        };
+        Stats {
+            min: sorted[0],
+            median,
+            mean: sorted.iter().sum::<Duration>() / sorted.len() as u32,
+        }
+    }
+}
+
+pub fn print_lines(runs: &[DayRun], time: bool) {
+    for run in runs {
+        if time {
+            println!("Day {:02} parse: {:.2?}", run.day, run.parse[0]);
+        }
+        for part in run.parts.iter() {
+            if time {
+                println!(
+                    "Day {:02} part {}: {} ({:.2?})",
+                    run.day, part.part, part.answer, part.durations[0]
+                );
+            } else {
+                println!("Day {:02} part {}: {}", run.day, part.part, part.answer);
+            }
+        }
+    }
+}
+
+// Prints the answers of several days as an aligned table. Parse durations get
+// their own rows, and count towards the total, only when `time` is set.
+pub fn print_table(runs: &[DayRun], time: bool) {
+    let mut rows: Vec<[String; 4]> = vec![];
+    let mut total = Duration::default();
+    for run in runs {
+        if time {
+            rows.push([
+                format!("{:02}", run.day),
+                "parse".to_string(),
+                String::new(),
+                format!("{:.2?}", run.parse[0]),
+            ]);
+            total += run.parse[0];
+        }
+        for part in run.parts.iter() {
+            rows.push([
+                format!("{:02}", run.day),
+                part.part.to_string(),
+                part.answer.to_string(),
+                format!("{:.2?}", part.durations[0]),
+            ]);
+            total += part.durations[0];
+        }
+    }
+    rows.push([
+        "Total".to_string(),
+        String::new(),
+        String::new(),
+        format!("{:.2?}", total),
+    ]);
+    print_aligned(
+        &["Day", "Part", "Answer", "Time"],
+        &rows,
+        &[false, true, false, true],
+    );
+}
+
+#[derive(Serialize)]
+struct Record<'a> {
+    day: u8,
+    part: u8,
+    answer: &'a Answer,
+    duration_ns: u64,
+}
+
+// Prints one JSON record per line for every part that was run.
+pub fn print_json(runs: &[DayRun]) {
+    for run in runs {
+        for part in run.parts.iter() {
+            let record = Record {
+                day: run.day,
+                part: part.part.number(),
+                answer: &part.answer,
+                duration_ns: part.durations[0].as_nanos() as u64,
+            };
+            println!(
+                "{}",
+                serde_json::to_string(&record).expect("unable to serialize record")
+            );
+        }
+    }
+}
+
+pub fn print_bench(runs: &[DayRun]) {
+    let mut rows: Vec<[String; 5]> = vec![];
+    for run in runs {
+        let mut steps = vec![("parse".to_string(), Stats::from_durations(&run.parse))];
+        for part in run.parts.iter() {
+            steps.push((
+                part.part.to_string(),
+                Stats::from_durations(&part.durations),
+            ));
+        }
+        for (step, stats) in steps {
+            rows.push([
+                format!("{:02}", run.day),
+                step,
+                format!("{:.2?}", stats.min),
+                format!("{:.2?}", stats.median),
+                format!("{:.2?}", stats.mean),
+            ]);
+        }
+    }
+    print_aligned(
+        &["Day", "Part", "Min", "Median", "Mean"],
+        &rows,
+        &[false, true, true, true, true],
+    );
+}
+
+fn print_aligned<R: AsRef<[String]>>(header: &[&str], rows: &[R], right: &[bool]) {
+    // Durations are printed in µs, so widths are counted in chars rather than bytes
+    let widths: Vec<usize> = header
+        .iter()
+        .enumerate()
+        .map(|(i, h)| {
+            rows.iter()
+                .map(|r| r.as_ref()[i].chars().count())
+                .chain(std::iter::once(h.len()))
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    let format_row = |cells: Vec<&str>| {
+        cells
+            .iter()
+            .enumerate()
+            .map(|(i, c)| {
+                if right[i] {
+                    format!("{:>w$}", c, w = widths[i])
+                } else {
+                    format!("{:<w$}", c, w = widths[i])
+                }
+            })
+            .collect::<Vec<String>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+    println!("{}", format_row(header.to_vec()));
+    for row in rows {
+        println!(
+            "{}",
+            format_row(row.as_ref().iter().map(|c| c.as_str()).collect())
+        );
+    }
+}
+
+#[test]
+fn test_stats_from_durations() {
+    let durations: Vec<Duration> = vec![5, 1, 4, 2]
+        .into_iter()
+        .map(Duration::from_millis)
+        .collect();
+    let expected = Stats {
+        min: Duration::from_millis(1),
+        median: Duration::from_millis(3),
+        mean: Duration::from_millis(3),
+    };
+    assert_eq!(expected, Stats::from_durations(&durations));
+}
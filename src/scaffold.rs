@@ -0,0 +1,105 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+const TEMPLATE: &str = include_str!("../templates/day.rs");
+
+lazy_static! {
+    static ref RE_MOD: Regex = Regex::new(r"^pub mod day_(\d+);$").expect("Invalid regex");
+    static ref RE_DAY: Regex = Regex::new(r"^\s+day!\((\d+), day_\d+\),$").expect("Invalid regex");
+}
+
+// Creates src/day_NN.rs from the template, the data/NN directory, and registers
+// the new module in src/lib.rs and in the registry. Paths are relative to the
+// repository root.
+pub fn new_day(number: u8, title: &str) -> io::Result<()> {
+    let module = format!("src/day_{:02}.rs", number);
+    if Path::new(&module).exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists", module),
+        ));
+    }
+
+    let lib = fs::read_to_string("src/lib.rs")?;
+    let lib = insert_day_line(
+        &lib,
+        &RE_MOD,
+        number,
+        &format!("pub mod day_{:02};", number),
+    )
+    .ok_or_else(|| invalid_data("no `pub mod day_NN;` found in src/lib.rs"))?;
+    let registry = fs::read_to_string("src/registry.rs")?;
+    let registry = insert_day_line(
+        &registry,
+        &RE_DAY,
+        number,
+        &format!("    day!({}, day_{:02}),", number, number),
+    )
+    .ok_or_else(|| invalid_data("no `day!(N, day_NN),` entry found in src/registry.rs"))?;
+
+    fs::write(&module, render(number, title))?;
+    fs::write("src/lib.rs", lib)?;
+    fs::write("src/registry.rs", registry)?;
+    fs::create_dir_all(format!("data/{:02}", number))?;
+    Ok(())
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn render(number: u8, title: &str) -> String {
+    TEMPLATE
+        .replace("{{number}}", &number.to_string())
+        .replace("{{title}}", title)
+}
+
+// Inserts `line` among the lines matched by `re`, whose first group is a day
+// number, keeping them in calendar order. Returns `None` if nothing matches.
+fn insert_day_line(content: &str, re: &Regex, number: u8, line: &str) -> Option<String> {
+    let lines: Vec<&str> = content.lines().collect();
+    let days: Vec<(usize, u8)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, l)| re.captures(l).map(|c| (i, c[1].parse().unwrap_or(0))))
+        .collect();
+    let position = match days.iter().find(|(_, n)| *n > number) {
+        Some((i, _)) => *i,
+        None => days.last()?.0 + 1,
+    };
+
+    let mut result: Vec<&str> = lines[..position].to_vec();
+    result.push(line);
+    result.extend(&lines[position..]);
+    Some(result.join("\n") + "\n")
+}
+
+#[test]
+fn test_insert_day_line() {
+    let content = "pub mod day_01;\npub mod day_03;\npub mod error;\n";
+    assert_eq!(
+        "pub mod day_01;\npub mod day_02;\npub mod day_03;\npub mod error;\n",
+        insert_day_line(content, &RE_MOD, 2, "pub mod day_02;").unwrap()
+    );
+    assert_eq!(
+        "pub mod day_01;\npub mod day_03;\npub mod day_04;\npub mod error;\n",
+        insert_day_line(content, &RE_MOD, 4, "pub mod day_04;").unwrap()
+    );
+    assert_eq!(
+        None,
+        insert_day_line("mod a;\n", &RE_MOD, 4, "pub mod day_04;")
+    );
+}
+
+#[test]
+fn test_insert_registry_entry() {
+    let content = "pub const DAYS: &[Day] = &[\n    day!(1, day_01),\n];\n";
+    assert_eq!(
+        "pub const DAYS: &[Day] = &[\n    day!(1, day_01),\n    day!(2, day_02),\n];\n",
+        insert_day_line(content, &RE_DAY, 2, "    day!(2, day_02),").unwrap()
+    );
+}
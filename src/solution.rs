@@ -0,0 +1,196 @@
+use serde::{Serialize, Serializer};
+use std::convert::TryFrom;
+use std::fmt;
+use std::io::Write;
+use std::str::FromStr;
+
+use crate::error::ParseError;
+
+/// The answer to one part of a puzzle.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Answer {
+    Number(i64),
+    Text(String),
+    /// A valid input for which the part has no answer.
+    None,
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+            Answer::None => write!(f, "no solution"),
+        }
+    }
+}
+
+// Numbers are kept as numbers in JSON output, so they can be compared as such.
+impl Serialize for Answer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Answer::Number(n) => serializer.serialize_i64(*n),
+            Answer::Text(s) => serializer.serialize_str(s),
+            Answer::None => serializer.serialize_none(),
+        }
+    }
+}
+
+// Numbers too large for an i64 are kept as text rather than wrapped around.
+macro_rules! answer_from_number {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    match i64::try_from(value) {
+                        Ok(n) => Answer::Number(n),
+                        Err(_) => Answer::Text(value.to_string()),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+answer_from_number!(i32, i64, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.to_string())
+    }
+}
+
+// Searches that may find nothing give `Answer::None`, printed as "no solution".
+impl<T: Into<Answer>> From<Option<T>> for Answer {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Answer::None, Into::into)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub fn number(self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(&self.number().to_string())
+    }
+}
+
+/// Which parts of a day to run, as given on the command line: `1`, `2` or `both`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Parts {
+    One,
+    Two,
+    Both,
+}
+
+impl Parts {
+    pub fn to_vec(self) -> Vec<Part> {
+        match self {
+            Parts::One => vec![Part::One],
+            Parts::Two => vec![Part::Two],
+            Parts::Both => vec![Part::One, Part::Two],
+        }
+    }
+}
+
+impl FromStr for Parts {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Parts::One),
+            "2" => Ok(Parts::Two),
+            "both" => Ok(Parts::Both),
+            _ => Err(format!("invalid part `{}`, expected 1, 2 or both", s)),
+        }
+    }
+}
+
+/// A day of the calendar: parses its puzzle input once, then solves each part
+/// from the parsed value.
+pub trait Solution {
+    fn parse(input: &str) -> Result<Self, ParseError>
+    where
+        Self: Sized;
+
+    fn part_one(&self) -> Answer;
+
+    fn part_two(&self) -> Answer;
+
+    fn solve(&self, part: Part) -> Answer {
+        match part {
+            Part::One => self.part_one(),
+            Part::Two => self.part_two(),
+        }
+    }
+
+    /// Solves `part`, writing a trace of how the answer was found to `out`.
+    /// Days without a trace just solve the part.
+    fn solve_verbose(&self, part: Part, out: &mut dyn Write) -> Answer {
+        let _ = out;
+        self.solve(part)
+    }
+}
+
+#[test]
+fn test_parse_parts() {
+    assert_eq!(Ok(Parts::One), "1".parse());
+    assert_eq!(Ok(Parts::Two), "2".parse());
+    assert_eq!(
+        vec![Part::One, Part::Two],
+        "both".parse::<Parts>().unwrap().to_vec()
+    );
+    assert!("3".parse::<Parts>().is_err());
+}
+
+#[test]
+fn test_serialize_answer() {
+    assert_eq!("42", serde_json::to_string(&Answer::from(42)).unwrap());
+    assert_eq!(
+        "\"67384529\"",
+        serde_json::to_string(&Answer::from("67384529")).unwrap()
+    );
+    assert_eq!(
+        "null",
+        serde_json::to_string(&Answer::from(None::<i64>)).unwrap()
+    );
+}
+
+#[test]
+fn test_answer_from_large_number() {
+    assert_eq!(Answer::Number(i64::MAX), Answer::from(i64::MAX as u64));
+    assert_eq!(
+        Answer::Text("18446744073709551615".to_string()),
+        Answer::from(u64::MAX)
+    );
+    assert_eq!(
+        "\"18446744073709551615\"",
+        serde_json::to_string(&Answer::from(u64::MAX)).unwrap()
+    );
+}
+
+#[test]
+fn test_answer_from_option() {
+    assert_eq!(Answer::Number(7), Answer::from(Some(7u32)));
+    assert_eq!(Answer::None, Answer::from(None::<String>));
+    assert_eq!("no solution", Answer::None.to_string());
+}
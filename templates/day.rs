@@ -0,0 +1,47 @@
+//! Day {{number}}: {{title}}
+
+use crate::error::{parse_lines, ParseError};
+use crate::solution::{Answer, Solution};
+
+/// The puzzle input.
+#[derive(Debug)]
+pub struct Puzzle {
+    lines: Vec<String>,
+}
+
+/// Parses the puzzle input.
+pub fn parse(input: &str) -> Result<Puzzle, ParseError> {
+    Ok(Puzzle {
+        lines: parse_lines(input, |line| Ok(line.to_string()))?,
+    })
+}
+
+/// TODO: solve part one; for now, the number of input lines.
+pub fn part_one(puzzle: &Puzzle) -> usize {
+    puzzle.lines.len()
+}
+
+/// TODO: solve part two; for now, the number of input lines.
+pub fn part_two(puzzle: &Puzzle) -> usize {
+    puzzle.lines.len()
+}
+
+/// Parses the puzzle input and solves both parts.
+pub fn solve(input: &str) -> Result<(usize, usize), ParseError> {
+    let puzzle = parse(input)?;
+    Ok((part_one(&puzzle), part_two(&puzzle)))
+}
+
+impl Solution for Puzzle {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        parse(input)
+    }
+
+    fn part_one(&self) -> Answer {
+        part_one(self).into()
+    }
+
+    fn part_two(&self) -> Answer {
+        part_two(self).into()
+    }
+}
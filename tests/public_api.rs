@@ -0,0 +1,31 @@
+use aoc2020::{day_01, day_07, day_13};
+
+#[test]
+fn test_solve_day_01() {
+    let input = "1721\n979\n366\n299\n675\n1456";
+    assert_eq!(
+        (Some(514579), Some(241861950)),
+        day_01::solve(input).unwrap()
+    );
+}
+
+#[test]
+fn test_create_graph() {
+    let graph = day_07::create_graph(vec![
+        "bright white bags contain 1 shiny gold bag.".to_string(),
+        "shiny gold bags contain no other bags.".to_string(),
+    ])
+    .unwrap();
+    assert_eq!(vec![(1, "shiny gold".to_string())], graph["bright white"]);
+}
+
+#[test]
+fn test_chinese_remainder() {
+    assert_eq!(Some(23), day_13::chinese_remainder(&[2, 3, 2], &[3, 5, 7]));
+}
+
+#[test]
+fn test_parse_error() {
+    let err = day_13::parse("939\n7,x,y").unwrap_err();
+    assert_eq!((2, 5), (err.line, err.column));
+}